@@ -0,0 +1,250 @@
+//! Synchronous versions of the network entry points of [`OJP`].
+//!
+//! Each call drives the async implementation to completion on a private current-thread
+//! runtime, so callers do not need to manage one. These functions must not be called from
+//! within an async runtime.
+
+use std::future::Future;
+
+use chrono::NaiveDateTime;
+
+use crate::{ClientConfig, OJP, OjpError, RequestBuilder, SimplifiedTrip, StopId, TokenProvider};
+
+fn block_on<F: Future>(future: F) -> Result<F::Output, OjpError> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(OjpError::RuntimeError)?;
+    Ok(runtime.block_on(future))
+}
+
+/// Blocking version of [`RequestBuilder::send_request`]
+pub fn send_request(request: RequestBuilder) -> Result<String, OjpError> {
+    Ok(block_on(request.send_request())??)
+}
+
+/// Blocking version of [`RequestBuilder::send_request_with`]
+pub fn send_request_with<P>(request: RequestBuilder, token_provider: &P) -> Result<String, OjpError>
+where
+    P: TokenProvider + ?Sized,
+{
+    Ok(block_on(request.send_request_with(token_provider))??)
+}
+
+/// Blocking version of [`OJP::find_location`]
+pub fn find_location<P>(
+    location: &str,
+    date_time: NaiveDateTime,
+    number_results: u32,
+    requestor_ref: &str,
+    token_provider: &P,
+) -> Result<Vec<i32>, OjpError>
+where
+    P: TokenProvider + ?Sized,
+{
+    find_location_with(
+        &ClientConfig::default(),
+        location,
+        date_time,
+        number_results,
+        requestor_ref,
+        token_provider,
+    )
+}
+
+/// Blocking version of [`OJP::find_location_with`]
+pub fn find_location_with<P>(
+    config: &ClientConfig,
+    location: &str,
+    date_time: NaiveDateTime,
+    number_results: u32,
+    requestor_ref: &str,
+    token_provider: &P,
+) -> Result<Vec<i32>, OjpError>
+where
+    P: TokenProvider + ?Sized,
+{
+    block_on(OJP::find_location_with(
+        config,
+        location,
+        date_time,
+        number_results,
+        requestor_ref,
+        token_provider,
+    ))?
+}
+
+/// Blocking version of [`OJP::find_trip`]
+pub fn find_trip<P>(
+    from_id: impl Into<StopId>,
+    to_id: impl Into<StopId>,
+    date_time: NaiveDateTime,
+    number_results: u32,
+    requestor_ref: &str,
+    token_provider: &P,
+) -> Result<SimplifiedTrip, OjpError>
+where
+    P: TokenProvider + ?Sized,
+{
+    find_trip_with(
+        &ClientConfig::default(),
+        from_id,
+        to_id,
+        date_time,
+        number_results,
+        requestor_ref,
+        token_provider,
+    )
+}
+
+/// Blocking version of [`OJP::find_trip_with`]
+pub fn find_trip_with<P>(
+    config: &ClientConfig,
+    from_id: impl Into<StopId>,
+    to_id: impl Into<StopId>,
+    date_time: NaiveDateTime,
+    number_results: u32,
+    requestor_ref: &str,
+    token_provider: &P,
+) -> Result<SimplifiedTrip, OjpError>
+where
+    P: TokenProvider + ?Sized,
+{
+    block_on(OJP::find_trip_with(
+        config,
+        from_id,
+        to_id,
+        date_time,
+        number_results,
+        requestor_ref,
+        token_provider,
+    ))?
+}
+
+/// Blocking version of [`OJP::find_stop_events`]
+pub fn find_stop_events<P>(
+    stop_id: impl Into<StopId>,
+    date_time: NaiveDateTime,
+    number_results: u32,
+    requestor_ref: &str,
+    token_provider: &P,
+) -> Result<OJP, OjpError>
+where
+    P: TokenProvider + ?Sized,
+{
+    find_stop_events_with(
+        &ClientConfig::default(),
+        stop_id,
+        date_time,
+        number_results,
+        requestor_ref,
+        token_provider,
+    )
+}
+
+/// Blocking version of [`OJP::find_stop_events_with`]
+pub fn find_stop_events_with<P>(
+    config: &ClientConfig,
+    stop_id: impl Into<StopId>,
+    date_time: NaiveDateTime,
+    number_results: u32,
+    requestor_ref: &str,
+    token_provider: &P,
+) -> Result<OJP, OjpError>
+where
+    P: TokenProvider + ?Sized,
+{
+    block_on(OJP::find_stop_events_with(
+        config,
+        stop_id,
+        date_time,
+        number_results,
+        requestor_ref,
+        token_provider,
+    ))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RequestError, StaticToken, TokenError};
+    use chrono::{Local, NaiveDate};
+    use secrecy::SecretString;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Answers the next request with `fixture`, returns the config to send it to
+    fn serve(fixture: &str) -> ClientConfig {
+        let body = std::fs::read_to_string(fixture).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 4096];
+            while !String::from_utf8_lossy(&request).contains("</OJP>") {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/xml\r\ncontent-length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        ClientConfig::new().set_url(&url)
+    }
+
+    fn token() -> StaticToken {
+        StaticToken::new(SecretString::new("token".into()))
+    }
+
+    fn date_time() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 10, 17)
+            .unwrap()
+            .and_hms_opt(8, 40, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn find_location_parses_response() {
+        let config = serve("test_xml/location_simple.xml");
+        let ids = find_location_with(&config, "Bern", date_time(), 3, "Test", &token()).unwrap();
+        assert_eq!(ids[0], 8507000);
+    }
+
+    #[test]
+    fn find_trip_parses_response() {
+        let config = serve("test_xml/trip_simple.xml");
+        let quay: StopId = "ch:1:sloid:3091:0:185066".parse().unwrap();
+        let trip =
+            find_trip_with(&config, quay, 8503088, date_time(), 3, "Test", &token()).unwrap();
+        assert_eq!(trip.number_of_legs(), 1);
+    }
+
+    #[test]
+    fn find_stop_events_parses_response() {
+        let config = serve("test_xml/stop_simple.xml");
+        let ojp =
+            find_stop_events_with(&config, 8507000, date_time(), 3, "Test", &token()).unwrap();
+        assert!(ojp.stop_events().is_some());
+    }
+
+    #[test]
+    fn missing_token_is_reported_without_runtime() {
+        let res = find_trip(
+            8503308,
+            8503424,
+            Local::now().naive_local(),
+            3,
+            "Test",
+            "OJP_RS_THIS_VARIABLE_DOES_NOT_EXIST",
+        );
+        assert!(matches!(
+            res,
+            Err(OjpError::RequestBuilderError(
+                RequestError::TokenProviderError(TokenError::EnvVar { .. })
+            ))
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::{self, Read, Write};
+
+use flate2::Compression;
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use flate2::write::GzEncoder;
+
+/// Value of the `Accept-Encoding` header when compressed responses are accepted
+pub(crate) const ACCEPT_ENCODING: &str = "gzip, deflate";
+
+/// Gzip-compresses a request body
+pub(crate) fn gzip(body: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body)?;
+    encoder.finish()
+}
+
+/// Decodes a response body according to its `Content-Encoding` header.
+/// Several codings are undone in the reverse order in which they were applied.
+pub(crate) fn decode(content_encoding: Option<&str>, body: &[u8]) -> io::Result<String> {
+    let mut body = body.to_vec();
+    let codings = content_encoding.unwrap_or_default().split(',').rev();
+    for coding in codings.map(str::trim).filter(|c| !c.is_empty()) {
+        body = match coding.to_lowercase().as_str() {
+            "identity" => body,
+            "gzip" | "x-gzip" => read_all(GzDecoder::new(body.as_slice()))?,
+            // "deflate" should be zlib-wrapped, but some servers send raw deflate streams
+            "deflate" => read_all(ZlibDecoder::new(body.as_slice()))
+                .or_else(|_| read_all(DeflateDecoder::new(body.as_slice())))?,
+            other => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("unsupported content encoding: {other}"),
+                ));
+            }
+        };
+    }
+    String::from_utf8(body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn read_all(mut reader: impl Read) -> io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OJP;
+
+    #[test]
+    fn gzip_fixture_matches_plain() {
+        let plain = std::fs::read_to_string("test_xml/stop_simple.xml").unwrap();
+        let compressed = std::fs::read("test_xml/stop_simple.xml.gz").unwrap();
+        assert!(compressed.len() < plain.len() / 10);
+        let decoded = decode(Some("gzip"), &compressed).unwrap();
+        assert_eq!(decoded, plain);
+        let _ojp = OJP::try_from(decoded.as_str()).unwrap();
+    }
+
+    #[test]
+    fn deflate_fixture_matches_plain() {
+        let plain = std::fs::read_to_string("test_xml/trip_lots.xml").unwrap();
+        let compressed = std::fs::read("test_xml/trip_lots.xml.zz").unwrap();
+        let decoded = decode(Some("deflate"), &compressed).unwrap();
+        assert_eq!(decoded, plain);
+        let ojp = OJP::try_from(decoded.as_str()).unwrap();
+        assert!(ojp.trips().is_some());
+    }
+
+    #[test]
+    fn gzip_roundtrip() {
+        let body = std::fs::read_to_string("test_xml/req_trip_simple.xml").unwrap();
+        let compressed = gzip(body.as_bytes()).unwrap();
+        assert_eq!(decode(Some("identity, gzip"), &compressed).unwrap(), body);
+    }
+
+    #[test]
+    fn unencoded_and_unknown_encodings() {
+        assert_eq!(decode(None, b"<OJP/>").unwrap(), "<OJP/>");
+        assert!(decode(Some("br"), b"<OJP/>").is_err());
+    }
+}
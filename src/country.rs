@@ -0,0 +1,168 @@
+//! ISO 3166 alpha-2 country codes and the UIC country codes used as the first two digits of
+//! DIDOK (UIC) stop numbers, see UIC leaflet 920-14.
+//!
+//! Some countries have several UIC codes (e.g. Bosnia and Herzegovina) and some countries share
+//! the code of a neighbour (e.g. Liechtenstein uses the Swiss one). The conversions return the
+//! primary code in both directions.
+
+/// `(ISO code, UIC code)`. The first entry of an ISO code is its primary UIC code, the first entry
+/// of a UIC code is its primary country.
+const COUNTRIES: &[(&str, i32)] = &[
+    ("fi", 10),
+    ("ru", 20),
+    ("by", 21),
+    ("ua", 22),
+    ("md", 23),
+    ("lt", 24),
+    ("lv", 25),
+    ("ee", 26),
+    ("kz", 27),
+    ("ge", 28),
+    ("uz", 29),
+    ("kp", 30),
+    ("mn", 31),
+    ("vn", 32),
+    ("cn", 33),
+    ("la", 34),
+    ("cu", 40),
+    ("al", 41),
+    ("jp", 42),
+    ("ba", 44),
+    ("pl", 51),
+    ("bg", 52),
+    ("ro", 53),
+    ("cz", 54),
+    ("hu", 55),
+    ("sk", 56),
+    ("az", 57),
+    ("am", 58),
+    ("kg", 59),
+    ("ie", 60),
+    ("kr", 61),
+    ("me", 62),
+    ("mk", 65),
+    ("tj", 66),
+    ("tm", 67),
+    ("af", 68),
+    ("gb", 70),
+    ("es", 71),
+    ("rs", 72),
+    ("gr", 73),
+    ("se", 74),
+    ("tr", 75),
+    ("no", 76),
+    ("hr", 78),
+    ("si", 79),
+    ("de", 80),
+    ("at", 81),
+    ("lu", 82),
+    ("it", 83),
+    ("nl", 84),
+    ("ch", 85),
+    ("dk", 86),
+    ("fr", 87),
+    ("be", 88),
+    ("tz", 89),
+    ("eg", 90),
+    ("tn", 91),
+    ("dz", 92),
+    ("ma", 93),
+    ("pt", 94),
+    ("il", 95),
+    ("ir", 96),
+    ("sy", 97),
+    ("lb", 98),
+    ("iq", 99),
+    // Additional codes of countries with several infrastructure managers
+    ("hu", 43),
+    ("ba", 49),
+    ("ba", 50),
+    // Countries without a code of their own
+    ("li", 85),
+    ("mc", 87),
+    ("sm", 83),
+    ("va", 83),
+];
+
+/// Primary UIC country code of an ISO 3166 alpha-2 code, case-insensitive
+pub fn iso_to_uic(iso: &str) -> Option<i32> {
+    let iso = iso.trim().to_lowercase();
+    COUNTRIES
+        .iter()
+        .find(|(code, _)| *code == iso)
+        .map(|(_, uic)| *uic)
+}
+
+/// Lowercase ISO 3166 alpha-2 code of the primary country of a UIC country code
+pub fn uic_to_iso(uic: i32) -> Option<&'static str> {
+    COUNTRIES
+        .iter()
+        .find(|(_, code)| *code == uic)
+        .map(|(iso, _)| *iso)
+}
+
+/// UIC country code of a DIDOK number, e.g. 85 for 8503000
+pub fn uic_of_didok(didok: i32) -> Option<i32> {
+    let uic = didok / 100_000;
+    uic_to_iso(uic).map(|_| uic)
+}
+
+/// Lowercase ISO 3166 alpha-2 code of the country of a DIDOK number, e.g. "de" for 8000105
+pub fn country_of_didok(didok: i32) -> Option<&'static str> {
+    uic_to_iso(didok / 100_000)
+}
+
+/// Lowercase form of an ISO 3166 alpha-2 code that has a UIC country code
+pub fn iso_code(iso: &str) -> Option<&'static str> {
+    let iso = iso.trim().to_lowercase();
+    COUNTRIES
+        .iter()
+        .find(|(code, _)| *code == iso)
+        .map(|(code, _)| *code)
+}
+
+/// Whether the ISO 3166 alpha-2 code has a UIC country code
+pub fn is_known(iso: &str) -> bool {
+    iso_code(iso).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primary_codes_roundtrip() {
+        for (iso, uic) in COUNTRIES {
+            if !["li", "mc", "sm", "va"].contains(iso) {
+                assert_eq!(uic_to_iso(iso_to_uic(iso).unwrap()), Some(*iso));
+            }
+            if ![43, 49, 50].contains(uic) {
+                assert_eq!(iso_to_uic(uic_to_iso(*uic).unwrap()), Some(*uic));
+            }
+        }
+        assert_eq!(iso_to_uic("CH"), Some(85));
+        assert_eq!(iso_to_uic("vn"), Some(32));
+        assert_eq!(iso_to_uic("nn"), None);
+        assert_eq!(iso_code(" LI"), Some("li"));
+        assert!(!is_known("xx"));
+    }
+
+    #[test]
+    fn shared_and_multiple_codes() {
+        assert_eq!(iso_to_uic("li"), Some(85));
+        assert_eq!(uic_to_iso(85), Some("ch"));
+        assert_eq!(iso_to_uic("ba"), Some(44));
+        assert_eq!(uic_to_iso(50), Some("ba"));
+        assert_eq!(uic_to_iso(43), Some("hu"));
+    }
+
+    #[test]
+    fn country_of_didok_numbers() {
+        assert_eq!(country_of_didok(8503000), Some("ch"));
+        assert_eq!(country_of_didok(8000105), Some("de"));
+        assert_eq!(country_of_didok(8727100), Some("fr"));
+        assert_eq!(country_of_didok(8300046), Some("it"));
+        assert_eq!(uic_of_didok(8101236), Some(81));
+        assert_eq!(country_of_didok(1234), None);
+    }
+}
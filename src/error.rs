@@ -0,0 +1,93 @@
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+
+/// Number of characters of a response body shown in error messages
+const PREVIEW_LENGTH: usize = 200;
+
+/// Coarse classification of errors, to decide whether to retry, skip or abort
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Network failures, timeouts, overloaded or unavailable service: retrying may succeed
+    Transient,
+    /// The request or the configuration is wrong (missing parameter, token, unknown stop, ...):
+    /// retrying will fail again until the caller is fixed
+    ClientMisuse,
+    /// The service refused the request or answered with something unusable
+    ServerRejection,
+    /// The request is valid, but the service has nothing for it (e.g. no trip found)
+    NoData,
+}
+
+impl ErrorKind {
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Transient)
+    }
+}
+
+/// Raw body of a response that could not be handled. Only a short preview is shown when
+/// displayed or debugged, the complete body is available through [`RawBody::as_str`].
+#[derive(Clone, PartialEq)]
+pub struct RawBody(Arc<str>);
+
+impl RawBody {
+    pub fn new(body: &str) -> Self {
+        RawBody(body.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The first characters of the body
+    pub fn preview(&self) -> &str {
+        match self.0.char_indices().nth(PREVIEW_LENGTH) {
+            Some((end, _)) => &self.0[..end],
+            None => &self.0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for RawBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.preview())?;
+        if self.preview().len() < self.len() {
+            write!(f, "... ({} bytes)", self.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for RawBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RawBody").field(&self.to_string()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_is_truncated() {
+        let body = "é".repeat(300);
+        let raw = RawBody::new(&body);
+        assert_eq!(raw.preview().chars().count(), PREVIEW_LENGTH);
+        assert_eq!(raw.as_str(), body);
+        assert!(raw.to_string().ends_with("... (600 bytes)"));
+    }
+
+    #[test]
+    fn short_body_is_complete() {
+        let raw = RawBody::new("<OJP/>");
+        assert_eq!(raw.preview(), "<OJP/>");
+        assert_eq!(raw.to_string(), "<OJP/>");
+    }
+}
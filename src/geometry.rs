@@ -0,0 +1,331 @@
+use std::fmt::Write;
+
+use chrono::{NaiveDateTime, SecondsFormat};
+use quick_xml::escape::escape;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{PtMode, SimplifiedLeg, SimplifiedTrip};
+
+/// WGS84 position of a point of a leg
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Coordinate {
+    longitude: f64,
+    latitude: f64,
+}
+
+impl Coordinate {
+    pub fn new(longitude: f64, latitude: f64) -> Self {
+        Coordinate {
+            longitude,
+            latitude,
+        }
+    }
+
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+}
+
+/// JSON string literal of `text`
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_option(text: Option<&str>) -> String {
+    text.map_or_else(|| "null".to_string(), json_string)
+}
+
+/// GeoJSON `LineString` through `coordinates`, `null` if there are fewer than two. Points
+/// with a NaN or infinite component have no JSON representation and are skipped.
+fn line_string(coordinates: &[Coordinate]) -> String {
+    let coordinates: Vec<_> = coordinates
+        .iter()
+        .filter(|c| c.longitude.is_finite() && c.latitude.is_finite())
+        .map(|c| format!("[{},{}]", c.longitude, c.latitude))
+        .collect();
+    if coordinates.len() < 2 {
+        return "null".to_string();
+    }
+    format!(
+        r#"{{"type":"LineString","coordinates":[{}]}}"#,
+        coordinates.join(",")
+    )
+}
+
+/// GeoJSON `FeatureCollection` with a feature per leg: its geometry, mode, line, stops and times
+pub(crate) fn geojson(trip: &SimplifiedTrip) -> String {
+    let features: Vec<_> = trip
+        .legs()
+        .into_iter()
+        .map(|leg| {
+            format!(
+                r#"{{"type":"Feature","geometry":{},"properties":{{"mode":{},"pt_mode":{},"line":{},"destination":{},"departure_stop":{},"arrival_stop":{},"departure_time":"{}","arrival_time":"{}"}}}}"#,
+                line_string(leg.geometry()),
+                json_string(leg.mode()),
+                json_option(leg.pt_mode().map(PtMode::code)),
+                json_option(leg.line()),
+                json_option(leg.destination()),
+                json_string(leg.departure_stop()),
+                json_string(leg.arrival_stop()),
+                rfc3339(leg.departure_time()),
+                rfc3339(leg.arrival_time())
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"type":"FeatureCollection","features":[{}]}}"#,
+        features.join(",")
+    )
+}
+
+/// UTC time as written in GeoJSON, GPX and KML
+pub(crate) fn rfc3339(time: NaiveDateTime) -> String {
+    time.and_utc().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Name of a leg in GPX and KML, e.g. "S4: Zürich Giesshübel - Zürich HB"
+fn leg_name(leg: &SimplifiedLeg) -> String {
+    format!(
+        "{}: {} - {}",
+        leg.line().unwrap_or(leg.mode()),
+        leg.departure_stop(),
+        leg.arrival_stop()
+    )
+}
+
+/// Stops with a known position where legs are boarded and alighted: name, position, time and
+/// type, "departure", "arrival" or "transfer". A stop where a leg departs from the stop where
+/// the previous one arrives is a single transfer at the arrival time.
+fn stops(trip: &SimplifiedTrip) -> Vec<(&str, Coordinate, NaiveDateTime, &'static str)> {
+    let legs = trip.legs();
+    // Whether the leg after `i` departs from the stop where `i` arrives
+    let transfers_after = |i: usize| {
+        legs.get(i + 1)
+            .is_some_and(|next| next.departure() == legs[i].arrival())
+    };
+    let mut stops = Vec::new();
+    for (i, leg) in legs.iter().enumerate() {
+        let after_transfer = i
+            .checked_sub(1)
+            .is_some_and(|prev| transfers_after(prev) && legs[prev].arrival_position().is_some());
+        if let (false, Some(position)) = (after_transfer, leg.departure_position()) {
+            stops.push((
+                leg.departure_stop(),
+                position,
+                leg.departure_time(),
+                "departure",
+            ));
+        }
+        if let Some(position) = leg.arrival_position() {
+            let kind = if transfers_after(i) {
+                "transfer"
+            } else {
+                "arrival"
+            };
+            stops.push((leg.arrival_stop(), position, leg.arrival_time(), kind));
+        }
+    }
+    stops
+}
+
+/// GPX 1.1 document with a waypoint per boarding and alighting stop and a track per leg
+pub(crate) fn gpx(trip: &SimplifiedTrip) -> String {
+    let mut gpx = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="ojp-rs" xmlns="http://www.topografix.com/GPX/1/1">
+"#,
+    );
+    for (name, position, time, kind) in stops(trip) {
+        let _ = writeln!(
+            gpx,
+            r#"  <wpt lat="{}" lon="{}"><time>{}</time><name>{}</name><type>{}</type></wpt>"#,
+            position.latitude,
+            position.longitude,
+            rfc3339(time),
+            escape(name),
+            kind
+        );
+    }
+    for leg in trip.legs().into_iter().filter(|l| !l.geometry().is_empty()) {
+        let _ = writeln!(
+            gpx,
+            "  <trk><name>{}</name><type>{}</type><trkseg>",
+            escape(leg_name(leg)),
+            escape(leg.mode())
+        );
+        for position in leg.geometry() {
+            let _ = writeln!(
+                gpx,
+                r#"    <trkpt lat="{}" lon="{}"/>"#,
+                position.latitude, position.longitude
+            );
+        }
+        gpx.push_str("  </trkseg></trk>\n");
+    }
+    gpx.push_str("</gpx>\n");
+    gpx
+}
+
+/// KML 2.2 document with a point per boarding and alighting stop and a line string per leg
+pub(crate) fn kml(trip: &SimplifiedTrip) -> String {
+    let mut kml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2">
+<Document>
+"#,
+    );
+    let _ = writeln!(
+        kml,
+        "  <name>{} - {}</name>",
+        escape(trip.departure_stop()),
+        escape(trip.arrival_stop())
+    );
+    for (name, position, time, _) in stops(trip) {
+        let _ = writeln!(
+            kml,
+            "  <Placemark><name>{}</name><TimeStamp><when>{}</when></TimeStamp><Point><coordinates>{},{}</coordinates></Point></Placemark>",
+            escape(name),
+            rfc3339(time),
+            position.longitude,
+            position.latitude
+        );
+    }
+    for leg in trip.legs().into_iter().filter(|l| !l.geometry().is_empty()) {
+        let coordinates: Vec<_> = leg
+            .geometry()
+            .iter()
+            .map(|p| format!("{},{}", p.longitude, p.latitude))
+            .collect();
+        let _ = writeln!(
+            kml,
+            "  <Placemark><name>{}</name><TimeSpan><begin>{}</begin><end>{}</end></TimeSpan><LineString><coordinates>{}</coordinates></LineString></Placemark>",
+            escape(leg_name(leg)),
+            rfc3339(leg.departure_time()),
+            rfc3339(leg.arrival_time()),
+            coordinates.join(" ")
+        );
+    }
+    kml.push_str("</Document>\n</kml>\n");
+    kml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_line_has_no_geometry() {
+        assert_eq!(line_string(&[Coordinate::new(8.5, 47.3)]), "null");
+        assert_eq!(
+            line_string(&[Coordinate::new(8.5, 47.3), Coordinate::new(8.6, 47.4)]),
+            r#"{"type":"LineString","coordinates":[[8.5,47.3],[8.6,47.4]]}"#
+        );
+    }
+
+    #[test]
+    fn non_finite_points_are_skipped() {
+        let line = [
+            Coordinate::new(8.5, 47.3),
+            Coordinate::new(f64::NAN, 47.35),
+            Coordinate::new(8.6, f64::INFINITY),
+            Coordinate::new(8.7, 47.5),
+        ];
+        assert_eq!(
+            line_string(&line),
+            r#"{"type":"LineString","coordinates":[[8.5,47.3],[8.7,47.5]]}"#
+        );
+        assert_eq!(line_string(&line[..3]), "null");
+
+        let time = NaiveDateTime::default();
+        let leg = SimplifiedLeg::new(1, "A", 2, "B", time, time, "bus".into())
+            .with_geometry(line.to_vec());
+        let trip = SimplifiedTrip::new(vec![leg]).unwrap();
+        let geojson: serde_json::Value = serde_json::from_str(&geojson(&trip)).unwrap();
+        assert_eq!(
+            geojson["features"][0]["geometry"]["coordinates"],
+            serde_json::json!([[8.5, 47.3], [8.7, 47.5]])
+        );
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(
+            json_string("Bahnhof \"Süd\"\\\n\u{1}"),
+            r#""Bahnhof \"Süd\"\\\n\u0001""#
+        );
+        assert_eq!(json_option(None), "null");
+    }
+
+    #[test]
+    fn transfer_stops_are_written_once() {
+        let time = |minute| {
+            NaiveDateTime::default()
+                .checked_add_signed(chrono::TimeDelta::minutes(minute))
+                .unwrap()
+        };
+        let (a, b, c) = (
+            Coordinate::new(8.5, 47.3),
+            Coordinate::new(8.6, 47.4),
+            Coordinate::new(8.7, 47.5),
+        );
+        let trip = SimplifiedTrip::new(vec![
+            SimplifiedLeg::new(1, "A", 2, "B", time(0), time(10), "Zug".into())
+                .with_positions(Some(a), Some(b)),
+            SimplifiedLeg::new(2, "B", 3, "C", time(15), time(30), "Bus".into())
+                .with_positions(Some(b), Some(c)),
+        ])
+        .unwrap();
+        assert_eq!(
+            stops(&trip),
+            [
+                ("A", a, time(0), "departure"),
+                ("B", b, time(10), "transfer"),
+                ("C", c, time(30), "arrival"),
+            ]
+        );
+        assert_eq!(gpx(&trip).matches("<wpt ").count(), 3);
+        assert_eq!(kml(&trip).matches("<Point>").count(), 3);
+    }
+
+    #[test]
+    fn names_are_escaped() {
+        let time = NaiveDateTime::default();
+        let leg = SimplifiedLeg::new(
+            1,
+            "Bahnhof & Post",
+            2,
+            "<Zentrum>",
+            time,
+            time,
+            "bus".into(),
+        )
+        .with_geometry(vec![Coordinate::new(8.5, 47.3), Coordinate::new(8.6, 47.4)]);
+        let trip = SimplifiedTrip::new(vec![leg]).unwrap();
+        for document in [gpx(&trip), kml(&trip)] {
+            assert!(document.contains("Bahnhof &amp; Post"));
+            assert!(document.contains("&lt;Zentrum&gt;"));
+        }
+    }
+}
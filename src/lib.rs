@@ -1,5 +1,43 @@
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "client")]
+mod compression;
+pub mod country;
+mod error;
+mod geometry;
+mod location;
+mod mode;
 mod model;
+mod occupancy;
+mod problem;
 mod requests;
+mod situation;
+mod stop_id;
+mod text;
+#[cfg(feature = "client")]
+mod token;
 
-pub use model::{LegType, OJP, OjpError, SimplifiedLeg, SimplifiedTrip, TripInfo, token};
-pub use requests::{RequestBuilder, RequestType};
+pub use error::{ErrorKind, RawBody};
+pub use geometry::Coordinate;
+pub use location::LocationId;
+pub use mode::{
+    AirSubmode, BusSubmode, CoachSubmode, FunicularSubmode, MetroSubmode, PtMode, RailSubmode,
+    SnowAndIceSubmode, Submode, TaxiSubmode, TelecabinSubmode, TramSubmode, TransportMode,
+    WaterSubmode,
+};
+#[cfg(feature = "client")]
+pub use model::token;
+pub use model::{
+    CallStatus, CallTime, Co2Emissions, LegType, OJP, OjpError, Platform, ServiceInfo,
+    SimplifiedCall, SimplifiedLeg, SimplifiedTrip, TripInfo,
+};
+pub use occupancy::{FareClass, Occupancy, OccupancyLevel};
+pub use problem::{Problem, ProblemType};
+#[cfg(feature = "client")]
+pub use requests::ClientConfig;
+pub use requests::{RequestBuilder, RequestError, RequestType};
+pub use situation::{PtSituation, SituationRef, SituationTexts, ValidityPeriod};
+pub use stop_id::{Sloid, StopId, didok_to_sloid};
+pub use text::Text;
+#[cfg(feature = "client")]
+pub use token::{EnvToken, FileToken, RefreshingToken, StaticToken, TokenError, TokenProvider};
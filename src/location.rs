@@ -0,0 +1,82 @@
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::stop_id::{Sloid, StopId};
+
+/// Start or end of a leg: a stop, or a place without stop such as a coordinate or an address
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LocationId {
+    /// DIDOK number or SLOID of the stop, as sent by the service
+    Stop(StopId),
+    GeoPosition {
+        longitude: f64,
+        latitude: f64,
+    },
+    /// Reference of the address, as sent by the service
+    Address(String),
+    /// Reference that could not be interpreted, as sent by the service
+    Unknown(String),
+}
+
+impl LocationId {
+    /// Id of the stop, if the location is a stop
+    pub fn stop_id(&self) -> Option<&StopId> {
+        match self {
+            Self::Stop(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// DIDOK number of the stop place, if the location is a stop
+    pub fn didok(&self) -> Option<i32> {
+        self.stop_id()?.didok().ok()
+    }
+
+    /// Whether both locations are the same place. Two stops are, if they belong to the same
+    /// stop place, e.g. two quays of a station.
+    pub fn same_place(&self, other: &LocationId) -> bool {
+        match (self, other) {
+            (Self::Stop(lhs), Self::Stop(rhs)) => lhs.same_stop_place(rhs),
+            _ => self == other,
+        }
+    }
+
+    pub fn is_stop(&self) -> bool {
+        matches!(self, Self::Stop(_))
+    }
+}
+
+impl From<i32> for LocationId {
+    fn from(id: i32) -> Self {
+        LocationId::Stop(id.into())
+    }
+}
+
+impl From<StopId> for LocationId {
+    fn from(id: StopId) -> Self {
+        LocationId::Stop(id)
+    }
+}
+
+impl From<Sloid> for LocationId {
+    fn from(sloid: Sloid) -> Self {
+        LocationId::Stop(sloid.into())
+    }
+}
+
+impl Display for LocationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stop(id) => write!(f, "stop {id}"),
+            Self::GeoPosition {
+                longitude,
+                latitude,
+            } => write!(f, "position {longitude}, {latitude}"),
+            Self::Address(address) => write!(f, "address {address}"),
+            Self::Unknown(reference) => write!(f, "unknown location {reference}"),
+        }
+    }
+}
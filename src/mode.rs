@@ -0,0 +1,364 @@
+use std::fmt::Display;
+
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Defines an enum of OJP/SIRI codes with a fallback for codes unknown to this crate
+macro_rules! code_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $code:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+        #[cfg_attr(feature = "serde", derive(Serialize))]
+        #[serde(from = "String")]
+        #[cfg_attr(feature = "serde", serde(into = "String"))]
+        pub enum $name {
+            $($variant,)*
+            /// Any code not known by this crate
+            Other(String),
+        }
+
+        impl $name {
+            pub fn from_code(code: &str) -> Self {
+                match code.trim() {
+                    $($code => Self::$variant,)*
+                    other => Self::Other(other.to_string()),
+                }
+            }
+
+            pub fn code(&self) -> &str {
+                match self {
+                    $(Self::$variant => $code,)*
+                    Self::Other(code) => code,
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(code: String) -> Self {
+                Self::from_code(&code)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.code().to_string()
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.code())
+            }
+        }
+    };
+}
+
+pub(crate) use code_enum;
+
+code_enum! {
+    /// Public transport mode of a service or stop, `PtMode` in OJP
+    PtMode {
+        All => "all",
+        Unknown => "unknown",
+        Air => "air",
+        Bus => "bus",
+        TrolleyBus => "trolleyBus",
+        Tram => "tram",
+        Coach => "coach",
+        Rail => "rail",
+        IntercityRail => "intercityRail",
+        UrbanRail => "urbanRail",
+        Metro => "metro",
+        Water => "water",
+        Ferry => "ferry",
+        Cableway => "cableway",
+        Telecabin => "telecabin",
+        Funicular => "funicular",
+        Lift => "lift",
+        Taxi => "taxi",
+        SelfDrive => "selfDrive",
+        SnowAndIce => "snowAndIce",
+    }
+}
+
+code_enum! {
+    /// `RailSubmode` of SIRI
+    RailSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        Local => "local",
+        HighSpeedRail => "highSpeedRail",
+        SuburbanRailway => "suburbanRailway",
+        RegionalRail => "regionalRail",
+        InterregionalRail => "interregionalRail",
+        LongDistance => "longDistance",
+        International => "international",
+        SleeperRailService => "sleeperRailService",
+        NightRail => "nightRail",
+        CarTransportRailService => "carTransportRailService",
+        TouristRailway => "touristRailway",
+        AirportLinkRail => "airportLinkRail",
+        RailShuttle => "railShuttle",
+        ReplacementRailService => "replacementRailService",
+        SpecialTrain => "specialTrain",
+        CrossCountryRail => "crossCountryRail",
+        RackAndPinionRailway => "rackAndPinionRailway",
+    }
+}
+
+code_enum! {
+    /// `CoachSubmode` of SIRI
+    CoachSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        InternationalCoach => "internationalCoach",
+        NationalCoach => "nationalCoach",
+        ShuttleCoach => "shuttleCoach",
+        RegionalCoach => "regionalCoach",
+        SpecialCoach => "specialCoach",
+        SightseeingCoach => "sightseeingCoach",
+        TouristCoach => "touristCoach",
+        CommuterCoach => "commuterCoach",
+        AllCoachServices => "allCoachServices",
+    }
+}
+
+code_enum! {
+    /// `MetroSubmode` of SIRI
+    MetroSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        Metro => "metro",
+        Tube => "tube",
+        UrbanRailway => "urbanRailway",
+        AllRailServices => "allRailServices",
+    }
+}
+
+code_enum! {
+    /// `BusSubmode` of SIRI
+    BusSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        LocalBus => "localBus",
+        RegionalBus => "regionalBus",
+        ExpressBus => "expressBus",
+        NightBus => "nightBus",
+        PostBus => "postBus",
+        SpecialNeedsBus => "specialNeedsBus",
+        MobilityBus => "mobilityBus",
+        MobilityBusForRegisteredDisabled => "mobilityBusForRegisteredDisabled",
+        SightseeingBus => "sightseeingBus",
+        ShuttleBus => "shuttleBus",
+        HighFrequencyBus => "highFrequencyBus",
+        DedicatedLaneBus => "dedicatedLaneBus",
+        SchoolBus => "schoolBus",
+        SchoolAndPublicServiceBus => "schoolAndPublicServiceBus",
+        RailReplacementBus => "railReplacementBus",
+        DemandAndResponseBus => "demandAndResponseBus",
+        AirportLinkBus => "airportLinkBus",
+    }
+}
+
+code_enum! {
+    /// `TramSubmode` of SIRI
+    TramSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        CityTram => "cityTram",
+        LocalTram => "localTram",
+        RegionalTram => "regionalTram",
+        SightseeingTram => "sightseeingTram",
+        ShuttleTram => "shuttleTram",
+        TrainTram => "trainTram",
+    }
+}
+
+code_enum! {
+    /// `WaterSubmode` of SIRI
+    WaterSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        InternationalCarFerry => "internationalCarFerry",
+        NationalCarFerry => "nationalCarFerry",
+        RegionalCarFerry => "regionalCarFerry",
+        LocalCarFerry => "localCarFerry",
+        InternationalPassengerFerry => "internationalPassengerFerry",
+        NationalPassengerFerry => "nationalPassengerFerry",
+        RegionalPassengerFerry => "regionalPassengerFerry",
+        LocalPassengerFerry => "localPassengerFerry",
+        PostBoat => "postBoat",
+        TrainFerry => "trainFerry",
+        RoadFerryLink => "roadFerryLink",
+        AirportBoatLink => "airportBoatLink",
+        HighSpeedVehicleService => "highSpeedVehicleService",
+        HighSpeedPassengerService => "highSpeedPassengerService",
+        SightseeingService => "sightseeingService",
+        SchoolBoat => "schoolBoat",
+        CableFerry => "cableFerry",
+        RiverBus => "riverBus",
+        ScheduledFerry => "scheduledFerry",
+        ShuttleFerryService => "shuttleFerryService",
+    }
+}
+
+code_enum! {
+    /// `AirSubmode` of SIRI
+    AirSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        InternationalFlight => "internationalFlight",
+        DomesticFlight => "domesticFlight",
+        IntercontinentalFlight => "intercontinentalFlight",
+        DomesticScheduledFlight => "domesticScheduledFlight",
+        ShuttleFlight => "shuttleFlight",
+        IntercontinentalCharterFlight => "intercontinentalCharterFlight",
+        InternationalCharterFlight => "internationalCharterFlight",
+        RoundTripCharterFlight => "roundTripCharterFlight",
+        SightseeingFlight => "sightseeingFlight",
+        HelicopterService => "helicopterService",
+        DomesticCharterFlight => "domesticCharterFlight",
+        SchengenAreaFlight => "SchengenAreaFlight",
+        AirshipService => "airshipService",
+        ShortHaulInternationalFlight => "shortHaulInternationalFlight",
+    }
+}
+
+code_enum! {
+    /// `TelecabinSubmode` of SIRI
+    TelecabinSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        Telecabin => "telecabin",
+        CableCar => "cableCar",
+        Lift => "lift",
+        ChairLift => "chairLift",
+        DragLift => "dragLift",
+        TelecabinLink => "telecabinLink",
+    }
+}
+
+code_enum! {
+    /// `FunicularSubmode` of SIRI
+    FunicularSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        Funicular => "funicular",
+        StreetCableCar => "streetCableCar",
+        AllFunicularServices => "allFunicularServices",
+        UndefinedFunicular => "undefinedFunicular",
+    }
+}
+
+code_enum! {
+    /// `TaxiSubmode` of SIRI
+    TaxiSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        CommunalTaxi => "communalTaxi",
+        WaterTaxi => "waterTaxi",
+        RailTaxi => "railTaxi",
+        BikeTaxi => "bikeTaxi",
+        BlackCab => "blackCab",
+        MiniCab => "miniCab",
+        AllTaxiServices => "allTaxiServices",
+    }
+}
+
+code_enum! {
+    /// `SnowAndIceSubmode` of SIRI
+    SnowAndIceSubmode {
+        Unknown => "unknown",
+        Undefined => "undefined",
+        SnowMobile => "snowMobile",
+        SnowCat => "snowCat",
+        SnowCoach => "snowCoach",
+        TerraBus => "terraBus",
+        WindSled => "windSled",
+    }
+}
+
+/// Submode of a [`PtMode`], e.g. `Rail(RailSubmode::RegionalRail)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Submode {
+    Rail(RailSubmode),
+    Coach(CoachSubmode),
+    Metro(MetroSubmode),
+    Bus(BusSubmode),
+    Tram(TramSubmode),
+    Water(WaterSubmode),
+    Air(AirSubmode),
+    Telecabin(TelecabinSubmode),
+    Funicular(FunicularSubmode),
+    Taxi(TaxiSubmode),
+    SnowAndIce(SnowAndIceSubmode),
+}
+
+/// Mode of a service or of a stop: the public transport mode and its submode, if any
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransportMode {
+    pt_mode: PtMode,
+    submode: Option<Submode>,
+}
+
+impl TransportMode {
+    pub fn new(pt_mode: PtMode, submode: Option<Submode>) -> Self {
+        TransportMode { pt_mode, submode }
+    }
+
+    pub fn pt_mode(&self) -> &PtMode {
+        &self.pt_mode
+    }
+
+    pub fn submode(&self) -> Option<&Submode> {
+        self.submode.as_ref()
+    }
+}
+
+impl Display for TransportMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.pt_mode)?;
+        if let Some(submode) = &self.submode {
+            write!(f, " ({})", submode.code())?;
+        }
+        Ok(())
+    }
+}
+
+impl Submode {
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Rail(submode) => submode.code(),
+            Self::Coach(submode) => submode.code(),
+            Self::Metro(submode) => submode.code(),
+            Self::Bus(submode) => submode.code(),
+            Self::Tram(submode) => submode.code(),
+            Self::Water(submode) => submode.code(),
+            Self::Air(submode) => submode.code(),
+            Self::Telecabin(submode) => submode.code(),
+            Self::Funicular(submode) => submode.code(),
+            Self::Taxi(submode) => submode.code(),
+            Self::SnowAndIce(submode) => submode.code(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_roundtrip() {
+        assert_eq!(PtMode::from_code("rail"), PtMode::Rail);
+        assert_eq!(PtMode::Telecabin.code(), "telecabin");
+        assert_eq!(BusSubmode::from_code("localBus").to_string(), "localBus");
+        assert_eq!(
+            PtMode::from_code("hovercraft"),
+            PtMode::Other("hovercraft".to_string())
+        );
+        assert_eq!(PtMode::from_code("hovercraft").code(), "hovercraft");
+    }
+}
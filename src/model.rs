@@ -1,113 +1,75 @@
 #![allow(dead_code)]
+use std::env::VarError;
 use std::fmt::Display;
+#[cfg(feature = "client")]
+use std::io::Write;
 use std::num::ParseIntError;
-use std::{env::VarError, io::Write};
 
 use chrono::{DateTime, Duration, NaiveDateTime, TimeDelta, Utc};
+#[cfg(feature = "client")]
 use futures::future::join_all;
 use quick_xml::DeError;
+#[cfg(feature = "client")]
 use secrecy::SecretString;
 use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
 use thiserror::Error;
+#[cfg(feature = "client")]
 use tracing::{Level, span};
 
-use crate::{RequestBuilder, RequestType, requests::RequestError};
-
+use crate::error::{ErrorKind, RawBody};
+use crate::geometry::{self, Coordinate};
+use crate::location::LocationId;
+use crate::mode::{
+    AirSubmode, BusSubmode, CoachSubmode, FunicularSubmode, MetroSubmode, PtMode, RailSubmode,
+    SnowAndIceSubmode, Submode, TaxiSubmode, TelecabinSubmode, TramSubmode, TransportMode,
+    WaterSubmode,
+};
+use crate::occupancy::{self, FareClass, Occupancy, OccupancyLevel};
+use crate::problem::{Problem, ProblemType};
+use crate::requests::RequestError;
+use crate::situation::{self, PtSituation, SituationFullRefs, SituationRef, Situations};
+use crate::stop_id::StopId;
+use crate::text::Text;
+#[cfg(feature = "client")]
+use crate::{ClientConfig, RequestBuilder, RequestType, TokenProvider};
+
+#[cfg(feature = "client")]
 pub fn token(api_key: &str) -> Result<SecretString, OjpError> {
     let t = std::env::var(api_key)?;
     Ok(SecretString::new(t.into()))
 }
 
-fn iso_to_uic(iso: &str) -> Option<i32> {
-    match iso.to_lowercase().as_str() {
-        "fi" => Some(10),
-        "ru" => Some(20),
-        "by" => Some(21),
-        "ua" => Some(22),
-        "md" => Some(23),
-        "lt" => Some(24),
-        "lv" => Some(25),
-        "ee" => Some(26),
-        "kz" => Some(27),
-        "ge" => Some(28),
-        "uz" => Some(29),
-        "kp" => Some(30),
-        "mn" => Some(31),
-        "nn" => Some(32),
-        "cn" => Some(33),
-        "la" => Some(34),
-        "cu" => Some(40),
-        "al" => Some(41),
-        "jp" => Some(42),
-        "ba" => Some(44),
-        "pl" => Some(51),
-        "bg" => Some(52),
-        "ro" => Some(53),
-        "cz" => Some(54),
-        "hu" => Some(55),
-        "sk" => Some(56),
-        "az" => Some(57),
-        "am" => Some(58),
-        "kg" => Some(59),
-        "ie" => Some(60),
-        "kr" => Some(61),
-        "me" => Some(62),
-        "mk" => Some(65),
-        "tj" => Some(66),
-        "tm" => Some(67),
-        "af" => Some(68),
-        "gb" => Some(70),
-        "es" => Some(71),
-        "rs" => Some(72),
-        "gr" => Some(73),
-        "se" => Some(74),
-        "tr" => Some(75),
-        "no" => Some(76),
-        "hr" => Some(78),
-        "si" => Some(79),
-        "de" => Some(80),
-        "at" => Some(81),
-        "lu" => Some(82),
-        "it" => Some(83),
-        "nl" => Some(84),
-        "ch" => Some(85),
-        "dk" => Some(86),
-        "fr" => Some(87),
-        "be" => Some(88),
-        "tz" => Some(89),
-        "eg" => Some(90),
-        "tn" => Some(91),
-        "dz" => Some(92),
-        "ma" => Some(93),
-        "pt" => Some(94),
-        "il" => Some(95),
-        "ir" => Some(96),
-        "sy" => Some(97),
-        "lb" => Some(98),
-        "iq" => Some(99),
-        _ => None,
-    }
-}
-
-fn sloid_to_didok(sloid: &str) -> Result<i32, OjpError> {
-    // Split SLOID into parts
-    let parts: Vec<&str> = sloid.split(':').collect();
-    if parts.len() < 4 {
-        return Err(OjpError::MalformedSloid(sloid.to_string())); // not enough parts
-    }
-
-    let iso = parts[0].to_lowercase();
-    let uic = iso_to_uic(iso.as_str()).ok_or_else(|| OjpError::FailedToConvertIsoCode(iso))?;
-
-    // Extract number and pad to 5 digits
-    let num_str = parts[3];
-    let num = num_str.parse::<i32>()?;
-
-    let didok = format!("{}{:05}", uic, num);
-    let didok = didok.parse::<i32>()?;
-
-    Ok(didok)
+/// Durations of the simplified model are (de)serialized as whole seconds
+#[cfg(feature = "serde")]
+mod duration_seconds {
+    use chrono::Duration;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        duration.num_seconds().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Duration::seconds(i64::deserialize(deserializer)?))
+    }
+}
+
+/// Location of a `StopPointRef` or `StopPlaceRef`, either a DIDOK number or a SLOID
+fn stop_ref_to_location(stop_ref: &str) -> LocationId {
+    match stop_ref.parse::<StopId>() {
+        Ok(id) => LocationId::Stop(id),
+        Err(_) => LocationId::Unknown(stop_ref.to_string()),
+    }
 }
+
 mod duration {
     use chrono::Duration;
     use serde::Deserialize;
@@ -225,12 +187,12 @@ mod duration {
 
 #[derive(Debug, Error)]
 pub enum OjpError {
-    #[error("Failed to parse XML {0}")]
-    FailedToParseXml(DeError, String),
+    #[error("Failed to parse XML {source}, response: {body}")]
+    FailedToParseXml { source: DeError, body: RawBody },
     #[error("Failed to find trip from id: {dep_id}, to id: {arr_id}. Optional message: {msg}")]
     FailedToFindTrip {
-        dep_id: i32,
-        arr_id: i32,
+        dep_id: StopId,
+        arr_id: StopId,
         msg: String,
     },
     #[error("Unkown LegType")]
@@ -239,16 +201,94 @@ pub enum OjpError {
     ParseInt(#[from] ParseIntError),
     #[error("Failed to convert to Simplified trip")]
     FailedToConvertToSimplifiedTrip,
+    #[error("A trip needs at least one leg")]
+    EmptyTrip,
+    #[error("Leg {leg} departs from {departure}, but the previous leg arrives at {arrival}")]
+    DisconnectedLegs {
+        leg: usize,
+        arrival: LocationId,
+        departure: LocationId,
+    },
+    #[error("Leg {leg} departs before the previous leg arrives or arrives before it departs")]
+    LegsOutOfOrder { leg: usize },
     #[error("Failed to get API token: {0}")]
     UnableToGetApiToken(#[from] VarError),
     #[error("Request building error: {0}")]
     RequestBuilderError(#[from] RequestError),
     #[error("No place results found")]
     PlaceResultsNotFound,
+    #[error("Trip from id: {dep_id}, to id: {arr_id} reported a problem: {problem}")]
+    TripProblem {
+        dep_id: StopId,
+        arr_id: StopId,
+        problem: Box<Problem>,
+    },
+    #[error("Location search for {location} reported a problem: {problem}")]
+    LocationProblem {
+        location: String,
+        problem: Box<Problem>,
+    },
+    #[error("Stop events at id: {stop_id} reported a problem: {problem}")]
+    StopEventProblem {
+        stop_id: StopId,
+        problem: Box<Problem>,
+    },
+    #[error("Leg endpoint is not a stop: {0}")]
+    NotAStop(LocationId),
+    #[error("Malformed stop id: {0}")]
+    MalformedStopId(String),
     #[error("Malformed sloid: {0}")]
     MalformedSloid(String),
     #[error("Failed to convert ISO code to UIC: {0}")]
     FailedToConvertIsoCode(String),
+    #[error("Occupancy level {0} has no severity to compare with")]
+    UnrankedOccupancyLevel(OccupancyLevel),
+    #[cfg(feature = "blocking")]
+    #[error("Failed to start the runtime of the blocking client: {0}")]
+    RuntimeError(std::io::Error),
+}
+
+impl OjpError {
+    /// Classifies the error, to decide whether to retry, skip or abort
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::FailedToParseXml { .. }
+            | Self::UnkownLegType
+            | Self::ParseInt(_)
+            | Self::FailedToConvertToSimplifiedTrip
+            | Self::EmptyTrip
+            | Self::DisconnectedLegs { .. }
+            | Self::LegsOutOfOrder { .. }
+            | Self::NotAStop(_)
+            | Self::MalformedStopId(_)
+            | Self::MalformedSloid(_)
+            | Self::FailedToConvertIsoCode(_) => ErrorKind::ServerRejection,
+            Self::FailedToFindTrip { .. } | Self::PlaceResultsNotFound => ErrorKind::NoData,
+            Self::UnableToGetApiToken(_) | Self::UnrankedOccupancyLevel(_) => {
+                ErrorKind::ClientMisuse
+            }
+            Self::RequestBuilderError(e) => e.kind(),
+            Self::TripProblem { problem, .. }
+            | Self::LocationProblem { problem, .. }
+            | Self::StopEventProblem { problem, .. } => problem.problem_type().kind(),
+            #[cfg(feature = "blocking")]
+            Self::RuntimeError(_) => ErrorKind::Transient,
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// Returns the problem reported by the OJP service, if this error originates from one
+    pub fn problem(&self) -> Option<&Problem> {
+        match self {
+            Self::TripProblem { problem, .. }
+            | Self::LocationProblem { problem, .. }
+            | Self::StopEventProblem { problem, .. } => Some(problem.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -257,24 +297,61 @@ pub struct OJP {
     ojp_response: OJPResponse,
 }
 
+/// Network entry points, they authenticate with the token of `token_provider`, see
+/// [`TokenProvider`]. The `*_with` versions send the requests with the settings of a
+/// [`ClientConfig`], the others with the default settings.
+#[cfg(feature = "client")]
 impl OJP {
-    pub async fn find_location(
+    /// Finds up to `number_results` stops matching `location`.
+    pub async fn find_location<P>(
+        location: &str,
+        date_time: NaiveDateTime,
+        number_results: u32,
+        requestor_ref: &str,
+        token_provider: &P,
+    ) -> Result<Vec<i32>, OjpError>
+    where
+        P: TokenProvider + ?Sized,
+    {
+        Self::find_location_with(
+            &ClientConfig::default(),
+            location,
+            date_time,
+            number_results,
+            requestor_ref,
+            token_provider,
+        )
+        .await
+    }
+
+    /// [`OJP::find_location`] with the settings of `config`
+    pub async fn find_location_with<P>(
+        config: &ClientConfig,
         location: &str,
         date_time: NaiveDateTime,
         number_results: u32,
         requestor_ref: &str,
-        api_key: &str,
-    ) -> Result<Vec<i32>, OjpError> {
+        token_provider: &P,
+    ) -> Result<Vec<i32>, OjpError>
+    where
+        P: TokenProvider + ?Sized,
+    {
         let response = RequestBuilder::new(date_time)
-            .set_token(token(api_key)?)
+            .set_client_config(config.clone())
             .set_name(location)
             .set_number_results(number_results)
             .set_request_type(RequestType::LocationInformation)
             .set_requestor_ref(requestor_ref)
-            .send_request()
+            .send_request_with(token_provider)
             .await?;
 
         let ojp = OJP::try_from(response.as_str())?;
+        if let Some(problem) = ojp.problem() {
+            return Err(OjpError::LocationProblem {
+                location: location.to_string(),
+                problem: Box::new(problem),
+            });
+        }
         let place_result = ojp
             .place_results()
             .ok_or(OjpError::PlaceResultsNotFound)?
@@ -284,18 +361,51 @@ impl OJP {
         Ok::<Vec<i32>, OjpError>(place_result)
     }
     /// Given an array of `&str` containing names of places, returns  Finds `number_results` trip `from_id` to `to_id` at `date_time` using the OJP API.
-    /// The name of the environment variable needs to be profived through the varibale `api_key`.
-    pub async fn find_locations(
+    pub async fn find_locations<P>(
+        locations: &[&str],
+        date_time: NaiveDateTime,
+        number_results: u32,
+        requestor_ref: &str,
+        token_provider: &P,
+    ) -> Result<Vec<i32>, OjpError>
+    where
+        P: TokenProvider + ?Sized,
+    {
+        Self::find_locations_with(
+            &ClientConfig::default(),
+            locations,
+            date_time,
+            number_results,
+            requestor_ref,
+            token_provider,
+        )
+        .await
+    }
+
+    /// [`OJP::find_locations`] with the settings of `config`
+    pub async fn find_locations_with<P>(
+        config: &ClientConfig,
         locations: &[&str],
         date_time: NaiveDateTime,
         number_results: u32,
         requestor_ref: &str,
-        api_key: &str,
-    ) -> Result<Vec<i32>, OjpError> {
+        token_provider: &P,
+    ) -> Result<Vec<i32>, OjpError>
+    where
+        P: TokenProvider + ?Sized,
+    {
         let point_ref = locations
             .iter()
             .map(|&tc| async move {
-                Self::find_location(tc, date_time, number_results, requestor_ref, api_key).await
+                Self::find_location_with(
+                    config,
+                    tc,
+                    date_time,
+                    number_results,
+                    requestor_ref,
+                    token_provider,
+                )
+                .await
             })
             .collect::<Vec<_>>();
         join_all(point_ref)
@@ -307,26 +417,54 @@ impl OJP {
 
     /// Finds `number_results` trips from a list of departures and arrivals at `date_time` using the OJP API.
     /// The length of `departures` and `arrivals` must be the same.
-    /// The name of the environment variable needs to be profived through the varibale `api_key`.
-    pub async fn find_trips(
-        departures: &[i32],
-        arrivals: &[i32],
+    pub async fn find_trips<P>(
+        departures: &[impl Into<StopId> + Clone],
+        arrivals: &[impl Into<StopId> + Clone],
+        date_time: NaiveDateTime,
+        number_results: u32,
+        requestor_ref: &str,
+        token_provider: &P,
+    ) -> Vec<Result<SimplifiedTrip, OjpError>>
+    where
+        P: TokenProvider + ?Sized,
+    {
+        Self::find_trips_with(
+            &ClientConfig::default(),
+            departures,
+            arrivals,
+            date_time,
+            number_results,
+            requestor_ref,
+            token_provider,
+        )
+        .await
+    }
+
+    /// [`OJP::find_trips`] with the settings of `config`
+    pub async fn find_trips_with<P>(
+        config: &ClientConfig,
+        departures: &[impl Into<StopId> + Clone],
+        arrivals: &[impl Into<StopId> + Clone],
         date_time: NaiveDateTime,
         number_results: u32,
         requestor_ref: &str,
-        api_key: &str,
-    ) -> Vec<Result<SimplifiedTrip, OjpError>> {
+        token_provider: &P,
+    ) -> Vec<Result<SimplifiedTrip, OjpError>>
+    where
+        P: TokenProvider + ?Sized,
+    {
         let ref_trips: Vec<_> = departures
             .iter()
             .zip(arrivals.iter())
-            .map(|(&from_id, &to_id)| async move {
-                Self::find_trip(
-                    from_id,
-                    to_id,
+            .map(|(from_id, to_id)| async move {
+                Self::find_trip_with(
+                    config,
+                    from_id.clone(),
+                    to_id.clone(),
                     date_time,
                     number_results,
                     requestor_ref,
-                    api_key,
+                    token_provider,
                 )
                 .await
             })
@@ -335,23 +473,51 @@ impl OJP {
     }
 
     /// Finds `number_results` trip `from_id` to `to_id` at `date_time` using the OJP API.
-    /// The name of the environment variable needs to be profived through the varibale `api_key`.
-    pub async fn find_trip(
-        from_id: i32,
-        to_id: i32,
+    pub async fn find_trip<P>(
+        from_id: impl Into<StopId>,
+        to_id: impl Into<StopId>,
+        date_time: NaiveDateTime,
+        number_results: u32,
+        requestor_ref: &str,
+        token_provider: &P,
+    ) -> Result<SimplifiedTrip, OjpError>
+    where
+        P: TokenProvider + ?Sized,
+    {
+        Self::find_trip_with(
+            &ClientConfig::default(),
+            from_id,
+            to_id,
+            date_time,
+            number_results,
+            requestor_ref,
+            token_provider,
+        )
+        .await
+    }
+
+    /// [`OJP::find_trip`] with the settings of `config`
+    pub async fn find_trip_with<P>(
+        config: &ClientConfig,
+        from_id: impl Into<StopId>,
+        to_id: impl Into<StopId>,
         date_time: NaiveDateTime,
         number_results: u32,
         requestor_ref: &str,
-        api_key: &str,
-    ) -> Result<SimplifiedTrip, OjpError> {
+        token_provider: &P,
+    ) -> Result<SimplifiedTrip, OjpError>
+    where
+        P: TokenProvider + ?Sized,
+    {
+        let (from_id, to_id) = (from_id.into(), to_id.into());
         let response = RequestBuilder::new(date_time)
-            .set_token(token(api_key)?)
-            .set_from(from_id)
-            .set_to(to_id)
+            .set_client_config(config.clone())
+            .set_from(from_id.clone())
+            .set_to(to_id.clone())
             .set_number_results(number_results)
             .set_request_type(RequestType::Trip)
             .set_requestor_ref(requestor_ref)
-            .send_request()
+            .send_request_with(token_provider)
             .await?;
 
         let ojp = OJP::try_from(response.as_str()).inspect_err(|e| {
@@ -361,15 +527,13 @@ impl OJP {
             let mut file = std::fs::File::create("debug.xml").unwrap();
             file.write_all(response.as_bytes()).unwrap();
         })?;
-        let ojp = if let Some(msg) = ojp.error() {
-            Err(OjpError::FailedToFindTrip {
+        if let Some(problem) = ojp.problem() {
+            return Err(OjpError::TripProblem {
                 dep_id: from_id,
                 arr_id: to_id,
-                msg: msg.to_string(),
-            })
-        } else {
-            Ok(ojp)
-        }?;
+                problem: Box::new(problem),
+            });
+        }
 
         let ref_trip =
             ojp.trip_departing_after(date_time, 0)
@@ -388,6 +552,62 @@ impl OJP {
         })
     }
 
+    /// Requests up to `number_results` departures from the stop `stop_id` after `date_time`.
+    pub async fn find_stop_events<P>(
+        stop_id: impl Into<StopId>,
+        date_time: NaiveDateTime,
+        number_results: u32,
+        requestor_ref: &str,
+        token_provider: &P,
+    ) -> Result<OJP, OjpError>
+    where
+        P: TokenProvider + ?Sized,
+    {
+        Self::find_stop_events_with(
+            &ClientConfig::default(),
+            stop_id,
+            date_time,
+            number_results,
+            requestor_ref,
+            token_provider,
+        )
+        .await
+    }
+
+    /// [`OJP::find_stop_events`] with the settings of `config`
+    pub async fn find_stop_events_with<P>(
+        config: &ClientConfig,
+        stop_id: impl Into<StopId>,
+        date_time: NaiveDateTime,
+        number_results: u32,
+        requestor_ref: &str,
+        token_provider: &P,
+    ) -> Result<OJP, OjpError>
+    where
+        P: TokenProvider + ?Sized,
+    {
+        let stop_id = stop_id.into();
+        let response = RequestBuilder::new(date_time)
+            .set_client_config(config.clone())
+            .set_stop(stop_id.clone())
+            .set_number_results(number_results)
+            .set_request_type(RequestType::StopEvent)
+            .set_requestor_ref(requestor_ref)
+            .send_request_with(token_provider)
+            .await?;
+
+        let ojp = OJP::try_from(response.as_str())?;
+        if let Some(problem) = ojp.problem() {
+            return Err(OjpError::StopEventProblem {
+                stop_id,
+                problem: Box::new(problem),
+            });
+        }
+        Ok(ojp)
+    }
+}
+
+impl OJP {
     /// Returns all trips from the OJP response
     pub fn trips(&self) -> Option<Vec<&TripResult>> {
         Some(
@@ -407,6 +627,62 @@ impl OJP {
         trips.first().map(|t| &t.trip)
     }
 
+    /// Returns the trips whose legs can all be travelled, i.e. without cancelled services or
+    /// skipped stops
+    pub fn feasible_trips(&self) -> Option<Vec<&TripResult>> {
+        let res = self
+            .trips()?
+            .into_iter()
+            .filter(|t| t.trip.is_feasible())
+            .collect::<Vec<_>>();
+        if res.is_empty() { None } else { Some(res) }
+    }
+
+    /// Returns the trips that are not more crowded than `max_level` in `fare_class`, empty if
+    /// none is. Trips without occupancy forecast are kept.
+    ///
+    /// Fails with [`OjpError::UnrankedOccupancyLevel`] if `max_level` has no
+    /// [`OccupancyLevel::severity`], i.e. for `Unknown` and `Other` levels.
+    pub fn trips_with_occupancy_at_most(
+        &self,
+        fare_class: &FareClass,
+        max_level: &OccupancyLevel,
+    ) -> Result<Vec<&TripResult>, OjpError> {
+        let max_severity = max_level
+            .severity()
+            .ok_or_else(|| OjpError::UnrankedOccupancyLevel(max_level.clone()))?;
+        Ok(self
+            .trips()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|t| {
+                t.trip
+                    .worst_occupancy(fare_class)
+                    .and_then(|l| l.severity())
+                    .is_none_or(|severity| severity <= max_severity)
+            })
+            .collect())
+    }
+
+    /// Returns all trips, the least crowded in `fare_class` first. Trips without occupancy
+    /// forecast come last.
+    pub fn trips_by_occupancy(&self, fare_class: &FareClass) -> Option<Vec<&TripResult>> {
+        let mut trips = self.trips()?;
+        trips.sort_by_key(|t| {
+            t.trip
+                .worst_occupancy(fare_class)
+                .and_then(|l| l.severity())
+                .unwrap_or(u8::MAX)
+        });
+        Some(trips)
+    }
+
+    pub fn fastest_feasible_trip(&self) -> Option<&Trip> {
+        let mut trips = self.feasible_trips()?;
+        trips.sort_by_key(|t| t.trip.duration);
+        trips.first().map(|t| &t.trip)
+    }
+
     // Returns references over all all PlaceResults
     pub fn place_results(&self) -> Option<Vec<&PlaceResult>> {
         Some(
@@ -420,6 +696,19 @@ impl OJP {
         )
     }
 
+    /// Returns all stop events (departures or arrivals at a stop) from the OJP response
+    pub fn stop_events(&self) -> Option<Vec<&StopEventResult>> {
+        Some(
+            self.ojp_response
+                .service_delivery
+                .ojp_stop_event_delivery
+                .as_ref()?
+                .stop_event_results
+                .iter()
+                .collect(),
+        )
+    }
+
     /// Returns all trips from the OJP response that are starting after `date_time`
     pub fn trips_departing_after(&self, date_time: NaiveDateTime) -> Option<Vec<&TripResult>> {
         let res = self
@@ -452,26 +741,95 @@ impl OJP {
         Some(&self.trips()?.get(index).copied()?.trip)
     }
 
-    // Returns the error message, if the OJP trip delivery returned an error (if no trip found for
+    /// Simplifies `trip`, one of the trips of this response, like `SimplifiedTrip::try_from`,
+    /// and sets the positions of its stops from the places of the response context
+    pub fn simplified_trip(&self, trip: &Trip) -> Result<SimplifiedTrip, OjpError> {
+        let places = self
+            .ojp_response
+            .service_delivery
+            .ojp_trip_delivery
+            .as_ref()
+            .and_then(|d| d.trip_response_context.as_ref())
+            .and_then(|c| c.places.as_ref());
+        simplify(trip, places)
+    }
+
+    /// Returns the situations (disruptions) of the response context, see
+    /// `RequestBuilder::set_include_situations`
+    pub fn situations(&self) -> Vec<&PtSituation> {
+        let service_delivery = &self.ojp_response.service_delivery;
+        let trip_situations = service_delivery
+            .ojp_trip_delivery
+            .as_ref()
+            .and_then(|d| d.trip_response_context.as_ref())
+            .and_then(|c| c.situations.as_ref());
+        let stop_event_situations = service_delivery
+            .ojp_stop_event_delivery
+            .as_ref()
+            .and_then(|d| d.stop_event_response_context.as_ref())
+            .and_then(|c| c.situations.as_ref());
+        trip_situations
+            .into_iter()
+            .chain(stop_event_situations)
+            .flat_map(|s| &s.pt_situations)
+            .collect()
+    }
+
+    /// Returns the situation `situation_ref` refers to
+    pub fn situation(&self, situation_ref: &SituationRef) -> Option<&PtSituation> {
+        self.situations()
+            .into_iter()
+            .find(|s| s.is_referenced_by(situation_ref))
+    }
+
+    /// Returns the situations affecting `trip`, referenced by its legs
+    pub fn trip_situations(&self, trip: &Trip) -> Vec<&PtSituation> {
+        trip.situation_refs()
+            .into_iter()
+            .filter_map(|r| self.situation(r))
+            .collect()
+    }
+
+    // Returns the error code, if the OJP delivery returned an error (if no trip found for
     // example)
     pub fn error(&self) -> Option<&str> {
-        Some(
-            self.ojp_response
-                .service_delivery
-                .ojp_trip_delivery
-                .as_ref()?
-                .error_condition
-                .as_ref()?
-                .trip_problem_type
-                .as_str(),
-        )
+        self.error_condition()?.code()
+    }
+
+    /// Returns the problem reported by the service, whatever the type of the delivery
+    pub fn problem(&self) -> Option<Problem> {
+        self.error_condition()?.problem()
+    }
+
+    fn error_condition(&self) -> Option<&ErrorCondition> {
+        let service_delivery = &self.ojp_response.service_delivery;
+        service_delivery
+            .ojp_trip_delivery
+            .as_ref()
+            .and_then(|d| d.error_condition.as_ref())
+            .or_else(|| {
+                service_delivery
+                    .ojp_location_information_delivery
+                    .as_ref()
+                    .and_then(|d| d.error_condition.as_ref())
+            })
+            .or_else(|| {
+                service_delivery
+                    .ojp_stop_event_delivery
+                    .as_ref()
+                    .and_then(|d| d.error_condition.as_ref())
+            })
+            .or(service_delivery.error_condition.as_ref())
     }
 }
 
 impl TryFrom<&str> for OJP {
     type Error = OjpError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        quick_xml::de::from_str(value).map_err(|e| OjpError::FailedToParseXml(e, value.to_string()))
+        quick_xml::de::from_str(value).map_err(|source| OjpError::FailedToParseXml {
+            source,
+            body: RawBody::new(value),
+        })
     }
 }
 
@@ -492,6 +850,7 @@ struct ServiceDelivery {
     ojp_location_information_delivery: Option<OJPLocationInformationDelivery>,
     #[serde(rename = "OJPStopEventDelivery")]
     ojp_stop_event_delivery: Option<OJPStopEventDelivery>,
+    error_condition: Option<ErrorCondition>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -501,103 +860,26 @@ struct TripResponseContext {
     situations: Option<Situations>,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct Situations {
-    #[serde(default)]
-    pt_situations: Vec<PtSituation>,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct PtSituation {
-    creation_time: DateTime<Utc>,
-    participation_ref: String,
-    situation_number: String,
-    version: i32,
-    source: Source,
-    validity_period: ValidityPeriod,
-    alert_cause: String,
-    priority: i32,
-    scope_type: String,
-    language: String,
-    #[serde(default)]
-    publishing_actions: Vec<PublishingAction>,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct PublishingAction {
-    // TODO: Both are present until now, but they is an error that say they are missing
-    // the even when present. Impossible to know why.
-    publish_at_scope: Option<PublishAtScope>,
-    passenger_sinformation_action: Option<PassengerInformationAction>,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct PassengerInformationAction {
-    #[serde(default)]
-    action_ref: String,
-    recorded_at_time: DateTime<Utc>,
-    perspective: String,
-    textual_content: TextualContent,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct TextualContent {
-    summary_content: SummaryText,
-    reason_content: ReasonText,
-    duration_content: DurationText,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct SummaryText {
-    summary_text: String,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct ReasonText {
-    reason_text: String,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct DurationText {
-    duration_text: String,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct PublishAtScope {
-    scope_type: String,
-    #[serde(default)]
-    affects: String,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct ValidityPeriod {
-    start_time: DateTime<Utc>,
-    end_time: DateTime<Utc>,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct Source {
-    #[serde(default)]
-    source_type: String,
-}
-
 #[derive(Deserialize, Debug)]
 struct Places {
     #[serde(rename = "Place", default)]
     places: Vec<Place>,
 }
 
+impl Places {
+    /// Position of the stop point or stop place referenced by `stop_ref`
+    fn position(&self, stop_ref: &str) -> Option<Coordinate> {
+        let place = self.places.iter().find(|p| {
+            p.stop_point.as_ref().map(|s| s.stop_point_ref.as_str()) == Some(stop_ref)
+                || p.stop_place.as_ref().map(|s| s.stop_place_ref.as_str()) == Some(stop_ref)
+        })?;
+        Some(Coordinate::new(
+            place.geo_position.longitude,
+            place.geo_position.latitude,
+        ))
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct OJPTripDelivery {
@@ -610,10 +892,101 @@ struct OJPTripDelivery {
     default_language: String,
 }
 
+/// `ErrorCondition` of a delivery: an OJP problem type and/or one of the SIRI errors
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct ErrorCondition {
-    trip_problem_type: String,
+    trip_problem_type: Option<String>,
+    location_problem_type: Option<String>,
+    stop_event_problem_type: Option<String>,
+    service_not_available_error: Option<SiriError>,
+    capability_not_supported_error: Option<SiriError>,
+    access_not_allowed_error: Option<SiriError>,
+    no_info_for_topic_error: Option<SiriError>,
+    beyond_data_horizon: Option<SiriError>,
+    unknown_participant_error: Option<SiriError>,
+    other_error: Option<SiriError>,
+    description: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct SiriError {
+    error_text: Option<String>,
+}
+
+impl ErrorCondition {
+    fn siri_errors(&self) -> [(&'static str, Option<&SiriError>); 7] {
+        [
+            (
+                "ServiceNotAvailableError",
+                self.service_not_available_error.as_ref(),
+            ),
+            (
+                "CapabilityNotSupportedError",
+                self.capability_not_supported_error.as_ref(),
+            ),
+            (
+                "AccessNotAllowedError",
+                self.access_not_allowed_error.as_ref(),
+            ),
+            ("NoInfoForTopicError", self.no_info_for_topic_error.as_ref()),
+            ("BeyondDataHorizon", self.beyond_data_horizon.as_ref()),
+            (
+                "UnknownParticipantError",
+                self.unknown_participant_error.as_ref(),
+            ),
+            ("OtherError", self.other_error.as_ref()),
+        ]
+    }
+
+    /// The OJP problem type if present, otherwise the error text of the SIRI error (which
+    /// often carries the OJP problem code), otherwise the name of the SIRI error
+    fn code(&self) -> Option<&str> {
+        let problem_type = self
+            .trip_problem_type
+            .as_deref()
+            .or(self.location_problem_type.as_deref())
+            .or(self.stop_event_problem_type.as_deref());
+        if problem_type.is_some() {
+            return problem_type;
+        }
+        let (name, error) = self
+            .siri_errors()
+            .into_iter()
+            .find_map(|(name, error)| Some((name, error?)))?;
+        Some(
+            error
+                .error_text
+                .as_deref()
+                .filter(|t| is_problem_code(t))
+                .unwrap_or(name),
+        )
+    }
+
+    fn problem(&self) -> Option<Problem> {
+        let code = self
+            .code()
+            .or(self.description.as_deref().filter(|d| is_problem_code(d)))?;
+        let error_text = self
+            .siri_errors()
+            .into_iter()
+            .find_map(|(_, error)| error?.error_text.as_deref())
+            .filter(|t| *t != code);
+        let description = self
+            .description
+            .as_deref()
+            .filter(|d| *d != code)
+            .or(error_text)
+            .map(str::to_string);
+        Some(Problem::new(ProblemType::from_code(code), description))
+    }
+}
+
+/// OJP problem codes are upper case identifiers such as `TRIP_NOTRIPFOUND`
+fn is_problem_code(text: &str) -> bool {
+    let text = text.trim();
+    text.contains('_') && text.chars().all(|c| c.is_ascii_uppercase() || c == '_')
 }
 
 #[derive(Deserialize, Debug)]
@@ -641,6 +1014,16 @@ pub struct Trip {
     distance: Option<u32>,
     #[serde(rename = "Leg", default)]
     legs: Vec<Leg>,
+    #[serde(default)]
+    unplanned: bool,
+    #[serde(default)]
+    cancelled: bool,
+    #[serde(default)]
+    deviation: bool,
+    #[serde(default)]
+    delayed: bool,
+    #[serde(default)]
+    infeasible: bool,
 }
 
 impl Trip {
@@ -667,54 +1050,551 @@ impl Trip {
             duration: self.duration,
         }
     }
-}
 
-/// Basic trip information: departure time, arrival time, and duration
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct TripInfo {
-    departure_time: NaiveDateTime,
-    arrival_time: NaiveDateTime,
-    duration: Duration,
-}
+    fn timed_legs(&self) -> impl DoubleEndedIterator<Item = &TimedLeg> {
+        self.legs.iter().filter_map(|l| l.timed_leg.as_ref())
+    }
 
-#[derive(Debug, Clone)]
-pub struct SimplifiedLeg {
-    departure_id: i32,
-    departure_stop: String,
-    arrival_id: i32,
-    arrival_stop: String,
-    departure_time: NaiveDateTime,
-    arrival_time: NaiveDateTime,
-    mode: String,
-}
+    /// Departure delay of the first timed leg
+    pub fn departure_delay(&self) -> Option<TimeDelta> {
+        self.timed_legs().next()?.departure_delay()
+    }
+
+    /// Arrival delay of the last timed leg
+    pub fn arrival_delay(&self) -> Option<TimeDelta> {
+        self.timed_legs().next_back()?.arrival_delay()
+    }
+
+    /// Whether the service sent an estimate for any call of the trip
+    pub fn has_realtime(&self) -> bool {
+        self.timed_legs().any(|tl| tl.has_realtime())
+    }
+
+    /// GeoJSON `FeatureCollection` of the legs, see [`SimplifiedTrip::to_geojson`]
+    pub fn to_geojson(&self) -> Result<String, OjpError> {
+        Ok(SimplifiedTrip::try_from(self)?.to_geojson())
+    }
+
+    /// GPX document of the legs, see [`SimplifiedTrip::to_gpx`]. Timed legs without projection
+    /// have no stop positions here, use `OJP::simplified_trip` for them.
+    pub fn to_gpx(&self) -> Result<String, OjpError> {
+        Ok(SimplifiedTrip::try_from(self)?.to_gpx())
+    }
+
+    /// KML document of the legs, see [`SimplifiedTrip::to_kml`]. Timed legs without projection
+    /// have no stop positions here, use `OJP::simplified_trip` for them.
+    pub fn to_kml(&self) -> Result<String, OjpError> {
+        Ok(SimplifiedTrip::try_from(self)?.to_kml())
+    }
+
+    /// Distance in meters, as sent by the service
+    pub fn distance(&self) -> Option<u32> {
+        self.distance
+    }
+
+    /// CO2 emitted per person. The length of a leg with an emission factor but without track
+    /// is deduced from the distance of the trip when it is the only leg of unknown length.
+    pub fn co2(&self) -> Co2Emissions {
+        let mut kilograms = 0.0;
+        let mut legs_without_data = 0;
+        let mut unknown_length = Vec::new();
+        for leg in &self.legs {
+            match leg.co2() {
+                Some(co2) => kilograms += co2,
+                None if leg.co2_factor().is_some() => unknown_length.push(leg),
+                None => legs_without_data += 1,
+            }
+        }
+        match (unknown_length.as_slice(), self.distance) {
+            ([leg], Some(distance)) => {
+                let known_length: Option<u32> = self
+                    .legs
+                    .iter()
+                    .filter(|l| l.transfer_leg.is_none() && !std::ptr::eq(*l, *leg))
+                    .map(Leg::length)
+                    .sum();
+                match known_length {
+                    Some(known_length) if distance >= known_length => {
+                        let factor = leg.co2_factor().unwrap_or_default();
+                        kilograms += factor * f64::from(distance - known_length) / 1000.0;
+                    }
+                    _ => legs_without_data += 1,
+                }
+            }
+            (legs, _) => legs_without_data += legs.len(),
+        }
+        Co2Emissions {
+            kilograms,
+            distance: self.distance,
+            legs_without_data,
+        }
+    }
+
+    /// Most crowded level of `fare_class` on any leg of the trip
+    pub fn worst_occupancy(&self, fare_class: &FareClass) -> Option<&OccupancyLevel> {
+        OccupancyLevel::worst(
+            self.timed_legs()
+                .filter_map(|tl| tl.worst_occupancy(fare_class)),
+        )
+    }
+
+    pub fn has_cancelled_legs(&self) -> bool {
+        self.timed_legs().any(|tl| tl.is_cancelled())
+    }
+
+    /// Whether the service marked the whole trip as cancelled, see also
+    /// [`Trip::has_cancelled_legs`]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Whether the trip uses services that are not in the timetable
+    pub fn is_unplanned(&self) -> bool {
+        self.unplanned
+    }
+
+    pub fn is_delayed(&self) -> bool {
+        self.delayed
+    }
+
+    /// Whether the trip or one of its legs deviates from the timetable
+    pub fn has_deviation(&self) -> bool {
+        self.deviation || self.timed_legs().any(|tl| tl.has_deviation())
+    }
+
+    /// Whether the trip can be travelled: the service did not mark it as cancelled or
+    /// infeasible and all its legs can be travelled
+    pub fn is_feasible(&self) -> bool {
+        !self.cancelled && !self.infeasible && self.timed_legs().all(|tl| tl.is_feasible())
+    }
+
+    /// Situations affecting any leg of the trip, without duplicates
+    pub fn situation_refs(&self) -> Vec<&SituationRef> {
+        situation::dedup(self.timed_legs().flat_map(|tl| tl.situation_refs()))
+    }
+}
+
+/// Basic trip information: departure time, arrival time, and duration
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TripInfo {
+    departure_time: NaiveDateTime,
+    arrival_time: NaiveDateTime,
+    #[cfg_attr(feature = "serde", serde(with = "duration_seconds"))]
+    duration: Duration,
+}
+
+impl TripInfo {
+    pub fn departure_time(&self) -> NaiveDateTime {
+        self.departure_time
+    }
+
+    pub fn arrival_time(&self) -> NaiveDateTime {
+        self.arrival_time
+    }
+
+    pub fn duration(&self) -> TimeDelta {
+        self.duration
+    }
+}
+
+/// CO2 emitted by a trip per person, see `Trip::co2`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Co2Emissions {
+    kilograms: f64,
+    distance: Option<u32>,
+    legs_without_data: usize,
+}
+
+impl Co2Emissions {
+    /// Emissions of the legs with data, in kilograms
+    pub fn kilograms(&self) -> f64 {
+        self.kilograms
+    }
+
+    /// Distance of the trip in meters
+    pub fn distance(&self) -> Option<u32> {
+        self.distance
+    }
+
+    /// Number of legs whose emissions are unknown and not included in [`Co2Emissions::kilograms`]
+    pub fn legs_without_data(&self) -> usize {
+        self.legs_without_data
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.legs_without_data == 0
+    }
+
+    /// Emissions of the same distance by car, given the CO2 the car emits per kilometer
+    pub fn car_kilograms(&self, car_kilograms_per_km: f64) -> Option<f64> {
+        Some(f64::from(self.distance?) / 1000.0 * car_kilograms_per_km)
+    }
+
+    /// Kilograms saved compared to the same distance by car, negative if the car emits less
+    pub fn saved_compared_to_car(&self, car_kilograms_per_km: f64) -> Option<f64> {
+        Some(self.car_kilograms(car_kilograms_per_km)? - self.kilograms)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimplifiedLeg {
+    departure: LocationId,
+    departure_stop: String,
+    arrival: LocationId,
+    arrival_stop: String,
+    departure_time: NaiveDateTime,
+    arrival_time: NaiveDateTime,
+    timetabled_departure_time: Option<NaiveDateTime>,
+    timetabled_arrival_time: Option<NaiveDateTime>,
+    departure_platform: Option<Platform>,
+    arrival_platform: Option<Platform>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    intermediates: Vec<SimplifiedCall>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    geometry: Vec<Coordinate>,
+    departure_position: Option<Coordinate>,
+    arrival_position: Option<Coordinate>,
+    mode: String,
+    transport_mode: Option<TransportMode>,
+    line: Option<String>,
+    destination: Option<String>,
+}
 
 impl SimplifiedLeg {
     pub fn new(
-        departure_id: i32,
+        departure: impl Into<LocationId>,
         departure_stop: &str,
-        arrival_id: i32,
+        arrival: impl Into<LocationId>,
         arrival_stop: &str,
         departure_time: NaiveDateTime,
         arrival_time: NaiveDateTime,
         mode: String,
     ) -> Self {
         SimplifiedLeg {
-            departure_id,
+            departure: departure.into(),
             departure_stop: departure_stop.to_string(),
-            arrival_id,
+            arrival: arrival.into(),
             arrival_stop: arrival_stop.to_string(),
             departure_time,
             arrival_time,
+            timetabled_departure_time: None,
+            timetabled_arrival_time: None,
+            departure_platform: None,
+            arrival_platform: None,
+            intermediates: Vec::new(),
+            geometry: Vec::new(),
+            departure_position: None,
+            arrival_position: None,
             mode,
+            transport_mode: None,
+            line: None,
+            destination: None,
         }
     }
+
+    pub fn departure(&self) -> &LocationId {
+        &self.departure
+    }
+
+    /// DIDOK number of the departure, if the leg starts at a stop
+    pub fn departure_id(&self) -> Option<i32> {
+        self.departure.didok()
+    }
+
+    /// Id of the departure stop with its quay, if the leg starts at a stop
+    pub fn departure_stop_id(&self) -> Option<&StopId> {
+        self.departure.stop_id()
+    }
+
+    /// Name of the departure stop or place
+    pub fn departure_stop(&self) -> &str {
+        &self.departure_stop
+    }
+
+    pub fn arrival(&self) -> &LocationId {
+        &self.arrival
+    }
+
+    /// DIDOK number of the arrival, if the leg ends at a stop
+    pub fn arrival_id(&self) -> Option<i32> {
+        self.arrival.didok()
+    }
+
+    /// Id of the arrival stop with its quay, if the leg ends at a stop
+    pub fn arrival_stop_id(&self) -> Option<&StopId> {
+        self.arrival.stop_id()
+    }
+
+    pub fn arrival_stop(&self) -> &str {
+        &self.arrival_stop
+    }
+
+    pub fn departure_time(&self) -> NaiveDateTime {
+        self.departure_time
+    }
+
+    pub fn arrival_time(&self) -> NaiveDateTime {
+        self.arrival_time
+    }
+
+    pub fn duration(&self) -> TimeDelta {
+        self.arrival_time - self.departure_time
+    }
+
+    /// Sets the timetabled times of a leg whose departure and arrival times are realtime
+    /// estimates
+    pub fn with_timetabled_times(
+        mut self,
+        departure_time: NaiveDateTime,
+        arrival_time: NaiveDateTime,
+    ) -> Self {
+        self.timetabled_departure_time = Some(departure_time);
+        self.timetabled_arrival_time = Some(arrival_time);
+        self
+    }
+
+    /// Timetabled departure time, the departure time if the leg has no realtime data
+    pub fn timetabled_departure_time(&self) -> NaiveDateTime {
+        self.timetabled_departure_time
+            .unwrap_or(self.departure_time)
+    }
+
+    /// Timetabled arrival time, the arrival time if the leg has no realtime data
+    pub fn timetabled_arrival_time(&self) -> NaiveDateTime {
+        self.timetabled_arrival_time.unwrap_or(self.arrival_time)
+    }
+
+    /// Whether the times of the leg are realtime estimates
+    pub fn has_realtime(&self) -> bool {
+        self.timetabled_departure_time.is_some() || self.timetabled_arrival_time.is_some()
+    }
+
+    pub fn departure_delay(&self) -> Option<TimeDelta> {
+        Some(self.departure_time - self.timetabled_departure_time?)
+    }
+
+    pub fn arrival_delay(&self) -> Option<TimeDelta> {
+        Some(self.arrival_time - self.timetabled_arrival_time?)
+    }
+
+    /// Sets the platforms where the leg departs and arrives
+    pub fn with_platforms(
+        mut self,
+        departure_platform: Option<Platform>,
+        arrival_platform: Option<Platform>,
+    ) -> Self {
+        self.departure_platform = departure_platform;
+        self.arrival_platform = arrival_platform;
+        self
+    }
+
+    /// Departure platform, only set for timed legs
+    pub fn departure_platform(&self) -> Option<&Platform> {
+        self.departure_platform.as_ref()
+    }
+
+    /// Arrival platform, only set for timed legs
+    pub fn arrival_platform(&self) -> Option<&Platform> {
+        self.arrival_platform.as_ref()
+    }
+
+    /// Whether the departure or the arrival platform changed
+    pub fn platform_changed(&self) -> bool {
+        self.departure_platform.iter().any(Platform::is_changed)
+            || self.arrival_platform.iter().any(Platform::is_changed)
+    }
+
+    /// Sets the stops served between the departure and the arrival
+    pub fn with_intermediates(mut self, intermediates: Vec<SimplifiedCall>) -> Self {
+        self.intermediates = intermediates;
+        self
+    }
+
+    /// Stops served between the departure and the arrival. Empty unless the trip was requested
+    /// with `RequestBuilder::set_include_intermediate_stops`.
+    pub fn intermediates(&self) -> &[SimplifiedCall] {
+        &self.intermediates
+    }
+
+    /// Sets the path of the leg, see [`LegType::geometry`]
+    pub fn with_geometry(mut self, geometry: Vec<Coordinate>) -> Self {
+        self.geometry = geometry;
+        self
+    }
+
+    pub fn geometry(&self) -> &[Coordinate] {
+        &self.geometry
+    }
+
+    /// Sets the positions of the departure and the arrival
+    pub fn with_positions(
+        mut self,
+        departure: Option<Coordinate>,
+        arrival: Option<Coordinate>,
+    ) -> Self {
+        self.departure_position = departure;
+        self.arrival_position = arrival;
+        self
+    }
+
+    /// Position of the departure, the first point of the geometry if it was not set
+    pub fn departure_position(&self) -> Option<Coordinate> {
+        self.departure_position.or(self.geometry.first().copied())
+    }
+
+    /// Position of the arrival, the last point of the geometry if it was not set
+    pub fn arrival_position(&self) -> Option<Coordinate> {
+        self.arrival_position.or(self.geometry.last().copied())
+    }
+
+    /// Localized name of the mode, e.g. "Zug" or "Fussweg"
+    pub fn mode(&self) -> &str {
+        &self.mode
+    }
+
+    /// Sets the public transport mode of the leg
+    pub fn with_transport_mode(mut self, transport_mode: TransportMode) -> Self {
+        self.transport_mode = Some(transport_mode);
+        self
+    }
+
+    /// Public transport mode, only set for timed legs
+    pub fn transport_mode(&self) -> Option<&TransportMode> {
+        self.transport_mode.as_ref()
+    }
+
+    pub fn pt_mode(&self) -> Option<&PtMode> {
+        Some(self.transport_mode.as_ref()?.pt_mode())
+    }
+
+    /// Sets the label of the line, e.g. "IC 8" or "S3"
+    pub fn with_line(mut self, line: &str) -> Self {
+        self.line = Some(line.to_string());
+        self
+    }
+
+    /// Sets the heading of the service, e.g. "Zürich HB"
+    pub fn with_destination(mut self, destination: &str) -> Self {
+        self.destination = Some(destination.to_string());
+        self
+    }
+
+    /// Label of the line, only set for timed legs
+    pub fn line(&self) -> Option<&str> {
+        self.line.as_deref()
+    }
+
+    /// Heading of the service, only set for timed legs
+    pub fn destination(&self) -> Option<&str> {
+        self.destination.as_deref()
+    }
 }
 
-#[derive(Debug, Clone)]
+/// Stop served between the departure and the arrival of a [`SimplifiedLeg`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimplifiedCall {
+    stop: LocationId,
+    stop_name: String,
+    arrival_time: Option<NaiveDateTime>,
+    departure_time: Option<NaiveDateTime>,
+    platform: Option<Platform>,
+}
+
+impl SimplifiedCall {
+    pub fn new(stop: impl Into<LocationId>, stop_name: &str) -> Self {
+        SimplifiedCall {
+            stop: stop.into(),
+            stop_name: stop_name.to_string(),
+            arrival_time: None,
+            departure_time: None,
+            platform: None,
+        }
+    }
+
+    pub fn with_arrival_time(mut self, arrival_time: NaiveDateTime) -> Self {
+        self.arrival_time = Some(arrival_time);
+        self
+    }
+
+    pub fn with_departure_time(mut self, departure_time: NaiveDateTime) -> Self {
+        self.departure_time = Some(departure_time);
+        self
+    }
+
+    pub fn with_platform(mut self, platform: Platform) -> Self {
+        self.platform = Some(platform);
+        self
+    }
+
+    pub fn stop(&self) -> &LocationId {
+        &self.stop
+    }
+
+    /// DIDOK number of the stop
+    pub fn stop_id(&self) -> Option<i32> {
+        self.stop.didok()
+    }
+
+    pub fn stop_name(&self) -> &str {
+        &self.stop_name
+    }
+
+    pub fn arrival_time(&self) -> Option<NaiveDateTime> {
+        self.arrival_time
+    }
+
+    pub fn departure_time(&self) -> Option<NaiveDateTime> {
+        self.departure_time
+    }
+
+    pub fn platform(&self) -> Option<&Platform> {
+        self.platform.as_ref()
+    }
+}
+
+impl From<&LegIntermediate> for SimplifiedCall {
+    fn from(intermediate: &LegIntermediate) -> Self {
+        let call = SimplifiedCall::new(intermediate.location(), intermediate.name());
+        let call = match intermediate.arrival_time() {
+            Some(time) => call.with_arrival_time(time.naive_utc()),
+            None => call,
+        };
+        let call = match intermediate.departure_time() {
+            Some(time) => call.with_departure_time(time.naive_utc()),
+            None => call,
+        };
+        match intermediate.platform() {
+            Some(platform) => call.with_platform(platform),
+            None => call,
+        }
+    }
+}
+
+/// A trip with at least one leg
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SimplifiedLegs"))]
 pub struct SimplifiedTrip {
     legs: Vec<SimplifiedLeg>,
 }
 
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct SimplifiedLegs {
+    legs: Vec<SimplifiedLeg>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SimplifiedLegs> for SimplifiedTrip {
+    type Error = OjpError;
+    fn try_from(value: SimplifiedLegs) -> Result<Self, Self::Error> {
+        SimplifiedTrip::new(value.legs)
+    }
+}
+
 impl Display for SimplifiedTrip {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(
@@ -739,47 +1619,147 @@ impl Display for SimplifiedTrip {
 }
 
 impl SimplifiedTrip {
-    pub fn new(legs: Vec<SimplifiedLeg>) -> Self {
-        SimplifiedTrip { legs }
+    /// Fails with [`OjpError::EmptyTrip`] if `legs` is empty
+    pub fn new(legs: Vec<SimplifiedLeg>) -> Result<Self, OjpError> {
+        if legs.is_empty() {
+            return Err(OjpError::EmptyTrip);
+        }
+        Ok(SimplifiedTrip { legs })
     }
+
+    /// Checks that every leg departs from the stop where the previous one arrives, and not
+    /// before it arrives
+    pub fn validate(&self) -> Result<(), OjpError> {
+        for (leg, l) in self.legs.iter().enumerate() {
+            if l.arrival_time < l.departure_time {
+                return Err(OjpError::LegsOutOfOrder { leg });
+            }
+            let Some(prev) = leg.checked_sub(1).map(|i| &self.legs[i]) else {
+                continue;
+            };
+            if !prev.arrival.same_place(&l.departure) {
+                return Err(OjpError::DisconnectedLegs {
+                    leg,
+                    arrival: prev.arrival.clone(),
+                    departure: l.departure.clone(),
+                });
+            }
+            if l.departure_time < prev.arrival_time {
+                return Err(OjpError::LegsOutOfOrder { leg });
+            }
+        }
+        Ok(())
+    }
+
+    fn first_leg(&self) -> &SimplifiedLeg {
+        // never empty, see `new`
+        &self.legs[0]
+    }
+
+    fn last_leg(&self) -> &SimplifiedLeg {
+        &self.legs[self.legs.len() - 1]
+    }
+
     pub fn legs(&self) -> Vec<&SimplifiedLeg> {
         self.legs.iter().collect()
     }
 
+    pub fn number_of_legs(&self) -> usize {
+        self.legs.len()
+    }
+
+    pub fn trip_info(&self) -> TripInfo {
+        TripInfo {
+            departure_time: self.departure_time(),
+            arrival_time: self.arrival_time(),
+            duration: self.duration(),
+        }
+    }
+
     pub fn departure_time(&self) -> NaiveDateTime {
-        self.legs().first().map(|l| l.departure_time).unwrap()
+        self.first_leg().departure_time
     }
 
     pub fn arrival_time(&self) -> NaiveDateTime {
-        self.legs().last().map(|l| l.arrival_time).unwrap()
+        self.last_leg().arrival_time
     }
 
     pub fn duration(&self) -> TimeDelta {
         self.arrival_time() - self.departure_time()
     }
 
-    pub fn departure_id(&self) -> i32 {
-        self.legs().first().map(|l| l.departure_id).unwrap()
+    /// Legs operated by a public transport service, i.e. with a transport mode
+    fn timed_legs(&self) -> impl DoubleEndedIterator<Item = &SimplifiedLeg> {
+        self.legs.iter().filter(|l| l.transport_mode.is_some())
+    }
+
+    /// Departure delay of the first timed leg, as `Trip::departure_delay`
+    pub fn departure_delay(&self) -> Option<TimeDelta> {
+        self.timed_legs().next()?.departure_delay()
+    }
+
+    /// Arrival delay of the last timed leg, as `Trip::arrival_delay`
+    pub fn arrival_delay(&self) -> Option<TimeDelta> {
+        self.timed_legs().next_back()?.arrival_delay()
+    }
+
+    pub fn has_realtime(&self) -> bool {
+        self.legs.iter().any(|l| l.has_realtime())
     }
 
-    pub fn arrival_id(&self) -> i32 {
-        self.legs().last().map(|l| l.arrival_id).unwrap()
+    pub fn departure(&self) -> &LocationId {
+        &self.first_leg().departure
+    }
+
+    pub fn arrival(&self) -> &LocationId {
+        &self.last_leg().arrival
+    }
+
+    /// DIDOK number of the departure, if the trip starts at a stop
+    pub fn departure_id(&self) -> Option<i32> {
+        self.departure().didok()
+    }
+
+    /// DIDOK number of the arrival, if the trip ends at a stop
+    pub fn arrival_id(&self) -> Option<i32> {
+        self.arrival().didok()
     }
 
     pub fn departure_stop(&self) -> &str {
-        self.legs()
-            .first()
-            .map(|l| l.departure_stop.as_str())
-            .unwrap()
+        &self.first_leg().departure_stop
     }
 
     pub fn arrival_stop(&self) -> &str {
-        self.legs().last().map(|l| l.arrival_stop.as_str()).unwrap()
+        &self.last_leg().arrival_stop
+    }
+
+    /// GeoJSON `FeatureCollection` with one `LineString` per leg and its mode, line, stops and
+    /// times as properties. Legs without [`SimplifiedLeg::geometry`] have a `null` geometry,
+    /// points with a NaN or infinite coordinate are left out.
+    pub fn to_geojson(&self) -> String {
+        geometry::geojson(self)
+    }
+
+    /// GPX document with a track per leg and a waypoint with the name and time of each stop
+    /// where a leg is boarded or alighted, once for transfers. Legs without
+    /// [`SimplifiedLeg::geometry`] have no track, stops without
+    /// [`SimplifiedLeg::departure_position`] or [`SimplifiedLeg::arrival_position`] no waypoint.
+    pub fn to_gpx(&self) -> String {
+        geometry::gpx(self)
+    }
+
+    /// KML document with a line string per leg and a point with the name and time of each stop
+    /// where a leg is boarded or alighted. Legs without [`SimplifiedLeg::geometry`] have no line
+    /// string, stops without a position no point.
+    pub fn to_kml(&self) -> String {
+        geometry::kml(self)
     }
 
     pub fn approx_equal(&self, rhs: &SimplifiedTrip, tolerance: f64) -> bool {
         // deprature and arrival must be the same
-        if self.departure_id() != rhs.departure_id() || self.arrival_id() != rhs.arrival_id() {
+        if !self.departure().same_place(rhs.departure())
+            || !self.arrival().same_place(rhs.arrival())
+        {
             return false;
         }
         // duration must be approximately equal
@@ -807,36 +1787,72 @@ impl SimplifiedTrip {
 impl TryFrom<&Trip> for SimplifiedTrip {
     type Error = OjpError;
     fn try_from(value: &Trip) -> Result<Self, Self::Error> {
-        let mut prev_arr_time = value.start_time.naive_utc();
-        let st: Vec<_> = value
-            .legs()
-            .into_iter()
-            .map(|leg| {
-                let typed_leg = LegType::try_from(leg)?;
-                let departure_id = typed_leg.departure_id()?;
-                let departure_stop = typed_leg.departure_stop();
-                let arrival_id = typed_leg.arrival_id()?;
-                let arrival_stop = typed_leg.arrival_stop();
-                let departure_time = typed_leg.departure_time().unwrap_or(prev_arr_time);
-                let arrival_time = typed_leg
-                    .arrival_time()
-                    .unwrap_or(prev_arr_time + typed_leg.duration());
-                prev_arr_time = arrival_time;
-                Ok(SimplifiedLeg::new(
-                    departure_id,
-                    departure_stop,
-                    arrival_id,
-                    arrival_stop,
-                    departure_time,
-                    arrival_time,
-                    typed_leg.mode().to_string(),
-                ))
-            })
-            .collect::<Result<Vec<_>, OjpError>>()?;
-        Ok(SimplifiedTrip { legs: st })
+        simplify(value, None)
     }
 }
 
+/// Simplifies `value`, the positions of its stops are looked up in `places`
+fn simplify(value: &Trip, places: Option<&Places>) -> Result<SimplifiedTrip, OjpError> {
+    let mut prev_arr_time = value.start_time.naive_utc();
+    let st: Vec<_> = value
+        .legs()
+        .into_iter()
+        .map(|leg| {
+            let typed_leg = LegType::try_from(leg)?;
+            let departure = typed_leg.departure_location();
+            let departure_stop = typed_leg.departure_stop();
+            let arrival = typed_leg.arrival_location();
+            let arrival_stop = typed_leg.arrival_stop();
+            let departure_time = typed_leg.departure_time().unwrap_or(prev_arr_time);
+            let arrival_time = typed_leg
+                .arrival_time()
+                .unwrap_or(prev_arr_time + typed_leg.duration());
+            prev_arr_time = arrival_time;
+            let simplified_leg = SimplifiedLeg::new(
+                departure,
+                departure_stop,
+                arrival,
+                arrival_stop,
+                departure_time,
+                arrival_time,
+                typed_leg.mode().to_string(),
+            );
+            let simplified_leg = match (
+                typed_leg.departure_call_time(),
+                typed_leg.arrival_call_time(),
+            ) {
+                (Some(departure), Some(arrival)) if typed_leg.has_realtime() => simplified_leg
+                    .with_timetabled_times(
+                        departure.timetabled().naive_utc(),
+                        arrival.timetabled().naive_utc(),
+                    ),
+                _ => simplified_leg,
+            }
+            .with_platforms(typed_leg.departure_platform(), typed_leg.arrival_platform())
+            .with_intermediates(
+                typed_leg
+                    .intermediates()
+                    .into_iter()
+                    .map(SimplifiedCall::from)
+                    .collect(),
+            )
+            .with_geometry(typed_leg.geometry())
+            .with_positions(
+                typed_leg.departure_position(places),
+                typed_leg.arrival_position(places),
+            );
+            Ok(match typed_leg.service() {
+                Some(service) => simplified_leg
+                    .with_transport_mode(service.transport_mode())
+                    .with_line(service.line_label())
+                    .with_destination(service.destination()),
+                None => simplified_leg,
+            })
+        })
+        .collect::<Result<Vec<_>, OjpError>>()?;
+    SimplifiedTrip::new(st)
+}
+
 pub enum LegType<'a> {
     Timed(&'a TimedLeg),
     Transfer(&'a TransferLeg),
@@ -868,6 +1884,100 @@ impl<'a> LegType<'a> {
         }
     }
 
+    /// Timetabled and estimated departure time, only timed legs have one
+    pub fn departure_call_time(&self) -> Option<CallTime> {
+        match *self {
+            Self::Timed(tl) => Some(tl.departure_call_time()),
+            Self::Transfer(_) | Self::Continuous(_) => None,
+        }
+    }
+
+    /// Timetabled and estimated arrival time, only timed legs have one
+    pub fn arrival_call_time(&self) -> Option<CallTime> {
+        match *self {
+            Self::Timed(tl) => Some(tl.arrival_call_time()),
+            Self::Transfer(_) | Self::Continuous(_) => None,
+        }
+    }
+
+    /// Delay of a timed leg, see `TimedLeg::delay`
+    pub fn delay(&self) -> Option<TimeDelta> {
+        match *self {
+            Self::Timed(tl) => tl.delay(),
+            Self::Transfer(_) | Self::Continuous(_) => None,
+        }
+    }
+
+    pub fn has_realtime(&self) -> bool {
+        match *self {
+            Self::Timed(tl) => tl.has_realtime(),
+            Self::Transfer(_) | Self::Continuous(_) => false,
+        }
+    }
+
+    /// Most crowded level of `fare_class` on a timed leg
+    pub fn worst_occupancy(&self, fare_class: &FareClass) -> Option<&'a OccupancyLevel> {
+        match *self {
+            Self::Timed(tl) => tl.worst_occupancy(fare_class),
+            Self::Transfer(_) | Self::Continuous(_) => None,
+        }
+    }
+
+    /// Stops served between boarding and alighting, only timed legs have some
+    pub fn intermediates(&self) -> Vec<&'a LegIntermediate> {
+        match *self {
+            Self::Timed(tl) => tl.intermediates(),
+            Self::Transfer(_) | Self::Continuous(_) => Vec::new(),
+        }
+    }
+
+    /// Whether a timed leg is cancelled, see `TimedLeg::is_cancelled`
+    pub fn is_cancelled(&self) -> bool {
+        match *self {
+            Self::Timed(tl) => tl.is_cancelled(),
+            Self::Transfer(_) | Self::Continuous(_) => false,
+        }
+    }
+
+    /// Whether the leg can be travelled, see `TimedLeg::is_feasible`
+    pub fn is_feasible(&self) -> bool {
+        match *self {
+            Self::Timed(tl) => tl.is_feasible(),
+            Self::Transfer(_) | Self::Continuous(_) => true,
+        }
+    }
+
+    /// Whether a timed leg deviates from the timetable, see `TimedLeg::has_deviation`
+    pub fn has_deviation(&self) -> bool {
+        match *self {
+            Self::Timed(tl) => tl.has_deviation(),
+            Self::Transfer(_) | Self::Continuous(_) => false,
+        }
+    }
+
+    /// Departure platform, only timed legs have one
+    pub fn departure_platform(&self) -> Option<Platform> {
+        match *self {
+            Self::Timed(tl) => tl.departure_platform(),
+            Self::Transfer(_) | Self::Continuous(_) => None,
+        }
+    }
+
+    /// Arrival platform, only timed legs have one
+    pub fn arrival_platform(&self) -> Option<Platform> {
+        match *self {
+            Self::Timed(tl) => tl.arrival_platform(),
+            Self::Transfer(_) | Self::Continuous(_) => None,
+        }
+    }
+
+    pub fn platform_changed(&self) -> bool {
+        match *self {
+            Self::Timed(tl) => tl.platform_changed(),
+            Self::Transfer(_) | Self::Continuous(_) => false,
+        }
+    }
+
     pub fn departure_stop(&'a self) -> &'a str {
         match *self {
             Self::Timed(tl) => tl.departure_stop(),
@@ -884,6 +1994,24 @@ impl<'a> LegType<'a> {
         }
     }
 
+    /// Name of the departure in all languages sent by the service
+    pub fn departure_stop_text(&self) -> &'a Text {
+        match *self {
+            Self::Timed(tl) => tl.departure_stop_text(),
+            Self::Transfer(t) => t.departure_stop_text(),
+            Self::Continuous(t) => t.departure_stop_text(),
+        }
+    }
+
+    /// Name of the arrival in all languages sent by the service
+    pub fn arrival_stop_text(&self) -> &'a Text {
+        match *self {
+            Self::Timed(tl) => tl.arrival_stop_text(),
+            Self::Transfer(t) => t.arrival_stop_text(),
+            Self::Continuous(t) => t.arrival_stop_text(),
+        }
+    }
+
     pub fn departure_id(&'a self) -> Result<i32, OjpError> {
         match *self {
             Self::Timed(tl) => tl.departure_id(),
@@ -900,6 +2028,62 @@ impl<'a> LegType<'a> {
         }
     }
 
+    pub fn departure_stop_id(&self) -> Result<StopId, OjpError> {
+        match *self {
+            Self::Timed(tl) => tl.departure_stop_id(),
+            Self::Transfer(t) => t.leg_start.stop_id(),
+            Self::Continuous(t) => t.leg_start.stop_id(),
+        }
+    }
+
+    pub fn arrival_stop_id(&self) -> Result<StopId, OjpError> {
+        match *self {
+            Self::Timed(tl) => tl.arrival_stop_id(),
+            Self::Transfer(t) => t.leg_end.stop_id(),
+            Self::Continuous(t) => t.leg_end.stop_id(),
+        }
+    }
+
+    pub fn departure_location(&self) -> LocationId {
+        match *self {
+            Self::Timed(tl) => tl.departure_location(),
+            Self::Transfer(t) => t.leg_start.location(),
+            Self::Continuous(t) => t.leg_start.location(),
+        }
+    }
+
+    pub fn arrival_location(&self) -> LocationId {
+        match *self {
+            Self::Timed(tl) => tl.arrival_location(),
+            Self::Transfer(t) => t.leg_end.location(),
+            Self::Continuous(t) => t.leg_end.location(),
+        }
+    }
+
+    /// Service operating the leg, only timed legs have one
+    pub fn service(&self) -> Option<ServiceInfo<'a>> {
+        match *self {
+            Self::Timed(tl) => Some(tl.service()),
+            Self::Transfer(_) | Self::Continuous(_) => None,
+        }
+    }
+
+    /// Situations affecting the leg, only timed legs have some
+    pub fn situation_refs(&self) -> Vec<&'a SituationRef> {
+        match *self {
+            Self::Timed(tl) => tl.situation_refs(),
+            Self::Transfer(_) | Self::Continuous(_) => Vec::new(),
+        }
+    }
+
+    /// Public transport mode of the leg, only timed legs have one
+    pub fn transport_mode(&self) -> Option<TransportMode> {
+        match *self {
+            Self::Timed(tl) => Some(tl.service.mode.transport_mode()),
+            Self::Transfer(_) | Self::Continuous(_) => None,
+        }
+    }
+
     pub fn mode(&self) -> &str {
         match *self {
             Self::Timed(tl) => tl.service.mode.name(),
@@ -907,6 +2091,43 @@ impl<'a> LegType<'a> {
             Self::Continuous(t) => t.service.personal_mode.as_str(),
         }
     }
+
+    /// Position of the departure: the start of transfer and continuous legs, or the stop looked
+    /// up in `places`
+    fn departure_position(&self, places: Option<&Places>) -> Option<Coordinate> {
+        match *self {
+            Self::Timed(tl) => places?.position(&tl.leg_board.stop_point_ref),
+            Self::Transfer(t) => t.leg_start.position(places),
+            Self::Continuous(t) => t.leg_start.position(places),
+        }
+    }
+
+    /// Position of the arrival, see [`LegType::departure_position`]
+    fn arrival_position(&self, places: Option<&Places>) -> Option<Coordinate> {
+        match *self {
+            Self::Timed(tl) => places?.position(&tl.leg_alight.stop_point_ref),
+            Self::Transfer(t) => t.leg_end.position(places),
+            Self::Continuous(t) => t.leg_end.position(places),
+        }
+    }
+
+    /// Path of the leg: the projection of its track if the trip was requested with
+    /// `RequestBuilder::set_include_leg_projection`, otherwise the positions of its start and
+    /// end. Empty for timed legs without projection, their stops have no position.
+    pub fn geometry(&self) -> Vec<Coordinate> {
+        let (track, endpoints) = match *self {
+            Self::Timed(tl) => (tl.leg_track.as_ref(), None),
+            Self::Transfer(t) => (None, Some((&t.leg_start, &t.leg_end))),
+            Self::Continuous(t) => (t.leg_track.as_ref(), Some((&t.leg_start, &t.leg_end))),
+        };
+        let positions = track.map(LegTrack::positions).unwrap_or_default();
+        if !positions.is_empty() {
+            return positions;
+        }
+        endpoints
+            .and_then(|(start, end)| Some(vec![start.coordinate()?, end.coordinate()?]))
+            .unwrap_or_default()
+    }
 }
 
 impl<'a> TryFrom<&'a Leg> for LegType<'a> {
@@ -937,6 +2158,40 @@ pub struct Leg {
     emission_co2: Option<EmissionCO2>,
 }
 
+impl Leg {
+    /// CO2 emitted per person and kilometer, as sent by the service
+    pub fn co2_factor(&self) -> Option<f64> {
+        Some(self.emission_co2.as_ref()?.kilogram_per_person_km)
+    }
+
+    /// Length in meters: the length of the track of a timed leg, the length of a continuous leg
+    pub fn length(&self) -> Option<u32> {
+        if let Some(tl) = &self.timed_leg {
+            tl.leg_track.as_ref()?.length()
+        } else {
+            u32::try_from(self.continuous_leg.as_ref()?.length).ok()
+        }
+    }
+
+    /// Whether the leg emits no CO2: transfers, walks and bike rides
+    pub fn is_emission_free(&self) -> bool {
+        self.transfer_leg.is_some()
+            || self
+                .continuous_leg
+                .as_ref()
+                .is_some_and(|cl| matches!(cl.service.personal_mode.as_str(), "foot" | "bicycle"))
+    }
+
+    /// CO2 emitted per person in kilograms. `None` if the service sent no emission factor or
+    /// the length of the leg is unknown.
+    pub fn co2(&self) -> Option<f64> {
+        if self.is_emission_free() {
+            return Some(0.0);
+        }
+        Some(self.co2_factor()? * f64::from(self.length()?) / 1000.0)
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct ContinuousLeg {
@@ -946,8 +2201,8 @@ pub struct ContinuousLeg {
     #[serde(with = "duration")]
     duration: Duration,
     length: i32,
-    leg_track: LegTrack,
-    path_guidance: PathGuidance,
+    leg_track: Option<LegTrack>,
+    path_guidance: Option<PathGuidance>,
 }
 
 impl ContinuousLeg {
@@ -959,6 +2214,14 @@ impl ContinuousLeg {
         self.leg_end.name()
     }
 
+    pub fn departure_stop_text(&self) -> &Text {
+        self.leg_start.name_text()
+    }
+
+    pub fn arrival_stop_text(&self) -> &Text {
+        self.leg_end.name_text()
+    }
+
     pub fn departure_id(&self) -> Result<i32, OjpError> {
         self.leg_start.id()
     }
@@ -1009,6 +2272,14 @@ impl TransferLeg {
         self.leg_end.name()
     }
 
+    pub fn departure_stop_text(&self) -> &Text {
+        self.leg_start.name_text()
+    }
+
+    pub fn arrival_stop_text(&self) -> &Text {
+        self.leg_end.name_text()
+    }
+
     pub fn departure_id(&self) -> Result<i32, OjpError> {
         self.leg_start.id()
     }
@@ -1021,20 +2292,64 @@ impl TransferLeg {
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct LegEndpoint {
-    stop_point_ref: String,
+    stop_point_ref: Option<String>,
+    stop_place_ref: Option<String>,
+    geo_position: Option<GeoPosition>,
+    address_ref: Option<String>,
     name: Text,
 }
 
 impl LegEndpoint {
+    /// DIDOK number of the stop, fails with [`OjpError::NotAStop`] for other places
     pub fn id(&self) -> Result<i32, OjpError> {
-        if let Ok(num) = self.stop_point_ref.parse::<i32>() {
-            Ok(num)
+        self.stop_id()?.didok()
+    }
+
+    pub fn stop_id(&self) -> Result<StopId, OjpError> {
+        match self.stop_ref() {
+            Some(stop_ref) => stop_ref.parse(),
+            None => Err(OjpError::NotAStop(self.location())),
+        }
+    }
+
+    fn stop_ref(&self) -> Option<&str> {
+        self.stop_point_ref
+            .as_deref()
+            .or(self.stop_place_ref.as_deref())
+    }
+
+    fn coordinate(&self) -> Option<Coordinate> {
+        let position = self.geo_position.as_ref()?;
+        Some(Coordinate::new(position.longitude, position.latitude))
+    }
+
+    /// Own position, or the position of the referenced stop in `places`
+    fn position(&self, places: Option<&Places>) -> Option<Coordinate> {
+        self.coordinate()
+            .or_else(|| places?.position(self.stop_ref()?))
+    }
+
+    pub fn location(&self) -> LocationId {
+        if let Some(stop_ref) = self.stop_ref() {
+            stop_ref_to_location(stop_ref)
+        } else if let Some(position) = &self.geo_position {
+            LocationId::GeoPosition {
+                longitude: position.longitude,
+                latitude: position.latitude,
+            }
+        } else if let Some(address) = &self.address_ref {
+            LocationId::Address(address.clone())
         } else {
-            sloid_to_didok(&self.stop_point_ref)
+            LocationId::Unknown(self.name().to_string())
         }
     }
+
     pub fn name(&self) -> &str {
-        self.name.text.as_str()
+        self.name.text()
+    }
+
+    pub fn name_text(&self) -> &Text {
+        &self.name
     }
 }
 
@@ -1050,20 +2365,128 @@ pub struct TimedLeg {
 }
 
 impl TimedLeg {
+    pub fn service(&self) -> ServiceInfo<'_> {
+        ServiceInfo::from(&self.service)
+    }
+
+    /// Situations affecting the service or the stops where it is boarded and alighted, without
+    /// duplicates
+    pub fn situation_refs(&self) -> Vec<&SituationRef> {
+        situation::dedup(
+            self.service
+                .situation_full_refs
+                .refs
+                .iter()
+                .chain(&self.leg_board.situation_full_refs.refs)
+                .chain(&self.leg_alight.situation_full_refs.refs),
+        )
+    }
+
+    /// Estimated departure time if there is one, timetabled time otherwise
     pub fn departure_time(&self) -> DateTime<Utc> {
-        if let Some(time) = self.leg_board.service_departure.estimated_time {
-            time
-        } else {
-            self.leg_board.service_departure.timetabled_time
-        }
+        self.departure_call_time().time()
+    }
+
+    pub fn departure_call_time(&self) -> CallTime {
+        self.leg_board.service_departure.call_time()
     }
 
+    /// Estimated arrival time if there is one, timetabled time otherwise
     pub fn arrival_time(&self) -> DateTime<Utc> {
-        if let Some(time) = self.leg_alight.service_arrival.estimated_time {
-            time
-        } else {
-            self.leg_alight.service_arrival.timetabled_time
-        }
+        self.arrival_call_time().time()
+    }
+
+    pub fn arrival_call_time(&self) -> CallTime {
+        self.leg_alight.service_arrival.call_time()
+    }
+
+    pub fn departure_delay(&self) -> Option<TimeDelta> {
+        self.departure_call_time().delay()
+    }
+
+    pub fn arrival_delay(&self) -> Option<TimeDelta> {
+        self.arrival_call_time().delay()
+    }
+
+    /// Delay of the leg: the arrival delay, or the departure delay if only the departure has
+    /// an estimate
+    pub fn delay(&self) -> Option<TimeDelta> {
+        self.arrival_delay().or_else(|| self.departure_delay())
+    }
+
+    pub fn departure_platform(&self) -> Option<Platform> {
+        self.leg_board.platform()
+    }
+
+    pub fn arrival_platform(&self) -> Option<Platform> {
+        self.leg_alight.platform()
+    }
+
+    /// Whether the departure or the arrival platform changed
+    pub fn platform_changed(&self) -> bool {
+        self.departure_platform().is_some_and(|p| p.is_changed())
+            || self.arrival_platform().is_some_and(|p| p.is_changed())
+    }
+
+    pub fn departure_status(&self) -> CallStatus {
+        self.leg_board.status
+    }
+
+    pub fn arrival_status(&self) -> CallStatus {
+        self.leg_alight.status
+    }
+
+    /// Whether the service is cancelled or skips the stop where it is boarded or alighted
+    pub fn is_cancelled(&self) -> bool {
+        self.service.cancelled
+            || self.departure_status().is_not_serviced()
+            || self.arrival_status().is_not_serviced()
+    }
+
+    /// Whether the leg can be travelled: the service runs and allows boarding and alighting
+    pub fn is_feasible(&self) -> bool {
+        !self.service.cancelled
+            && self.departure_status().can_board()
+            && self.arrival_status().can_alight()
+    }
+
+    /// Whether the service deviates from its route or adds or skips a stop of the leg
+    pub fn has_deviation(&self) -> bool {
+        self.service.deviation
+            || [self.departure_status(), self.arrival_status()]
+                .into_iter()
+                .chain(self.leg_intermediates.iter().map(|i| i.status()))
+                .any(|s| s.is_unplanned() || s.is_not_serviced())
+    }
+
+    /// Expected occupancy per fare class when the service departs from the boarding stop
+    pub fn occupancies(&self) -> &[Occupancy] {
+        &self.leg_board.expected_departure_occupancies
+    }
+
+    /// Most crowded level of `fare_class` between boarding and alighting
+    pub fn worst_occupancy(&self, fare_class: &FareClass) -> Option<&OccupancyLevel> {
+        OccupancyLevel::worst(
+            occupancy::level_of(self.occupancies(), fare_class)
+                .into_iter()
+                .chain(
+                    self.leg_intermediates
+                        .iter()
+                        .filter_map(|i| i.occupancy(fare_class)),
+                ),
+        )
+    }
+
+    /// Stops served between boarding and alighting, in the order they are served
+    pub fn intermediates(&self) -> Vec<&LegIntermediate> {
+        self.leg_intermediates.iter().collect()
+    }
+
+    /// Whether the service sent an estimate for any call of the leg
+    pub fn has_realtime(&self) -> bool {
+        self.departure_call_time().is_realtime()
+            || self.arrival_call_time().is_realtime()
+            || self.leg_intermediates.iter().any(|i| i.has_realtime())
     }
 
     pub fn departure_id(&self) -> Result<i32, OjpError> {
@@ -1074,6 +2497,24 @@ impl TimedLeg {
         self.leg_alight.id()
     }
 
+    /// Stop point where the leg departs, usually the SLOID of a quay
+    pub fn departure_stop_id(&self) -> Result<StopId, OjpError> {
+        self.leg_board.stop_id()
+    }
+
+    /// Stop point where the leg arrives, usually the SLOID of a quay
+    pub fn arrival_stop_id(&self) -> Result<StopId, OjpError> {
+        self.leg_alight.stop_id()
+    }
+
+    pub fn departure_location(&self) -> LocationId {
+        stop_ref_to_location(&self.leg_board.stop_point_ref)
+    }
+
+    pub fn arrival_location(&self) -> LocationId {
+        stop_ref_to_location(&self.leg_alight.stop_point_ref)
+    }
+
     pub fn departure_stop(&self) -> &str {
         self.leg_board.name()
     }
@@ -1081,20 +2522,111 @@ impl TimedLeg {
     pub fn arrival_stop(&self) -> &str {
         self.leg_alight.name()
     }
+
+    pub fn departure_stop_text(&self) -> &Text {
+        self.leg_board.name_text()
+    }
+
+    pub fn arrival_stop_text(&self) -> &Text {
+        self.leg_alight.name_text()
+    }
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
-struct LegIntermediate {
+pub struct LegIntermediate {
     stop_point_ref: String,
     stop_point_name: Text,
     name_suffix: Option<Text>,
     planned_quay: Option<Text>,
+    estimated_quay: Option<Text>,
     service_arrival: Option<ServiceArrival>,
     service_departure: Option<ServiceDeparture>,
     order: u32,
     #[serde(rename = "ExpectedDepartureOccupancy", default)]
-    expected_departure_occupancies: Vec<ExpectedDepartureOccupancy>,
+    expected_departure_occupancies: Vec<Occupancy>,
+    #[serde(flatten)]
+    status: CallStatus,
+}
+
+impl LegIntermediate {
+    pub fn stop_point_ref(&self) -> &str {
+        &self.stop_point_ref
+    }
+
+    /// DIDOK number of the stop
+    pub fn id(&self) -> Result<i32, OjpError> {
+        self.stop_id()?.didok()
+    }
+
+    pub fn stop_id(&self) -> Result<StopId, OjpError> {
+        self.stop_point_ref.parse()
+    }
+
+    pub fn location(&self) -> LocationId {
+        stop_ref_to_location(&self.stop_point_ref)
+    }
+
+    pub fn name(&self) -> &str {
+        self.stop_point_name.text()
+    }
+
+    pub fn name_text(&self) -> &Text {
+        &self.stop_point_name
+    }
+
+    /// Position of the stop in the journey of the service
+    pub fn order(&self) -> u32 {
+        self.order
+    }
+
+    /// Expected occupancy per fare class when the service departs from the stop
+    pub fn occupancies(&self) -> &[Occupancy] {
+        &self.expected_departure_occupancies
+    }
+
+    pub fn occupancy(&self, fare_class: &FareClass) -> Option<&OccupancyLevel> {
+        occupancy::level_of(&self.expected_departure_occupancies, fare_class)
+    }
+
+    /// Estimated arrival time if there is one, timetabled time otherwise
+    pub fn arrival_time(&self) -> Option<DateTime<Utc>> {
+        Some(self.arrival_call_time()?.time())
+    }
+
+    /// Estimated departure time if there is one, timetabled time otherwise
+    pub fn departure_time(&self) -> Option<DateTime<Utc>> {
+        Some(self.departure_call_time()?.time())
+    }
+
+    pub fn status(&self) -> CallStatus {
+        self.status
+    }
+
+    pub fn arrival_call_time(&self) -> Option<CallTime> {
+        Some(self.service_arrival.as_ref()?.call_time())
+    }
+
+    pub fn departure_call_time(&self) -> Option<CallTime> {
+        Some(self.service_departure.as_ref()?.call_time())
+    }
+
+    pub fn arrival_delay(&self) -> Option<TimeDelta> {
+        self.arrival_call_time()?.delay()
+    }
+
+    pub fn departure_delay(&self) -> Option<TimeDelta> {
+        self.departure_call_time()?.delay()
+    }
+
+    pub fn has_realtime(&self) -> bool {
+        self.arrival_call_time().is_some_and(|t| t.is_realtime())
+            || self.departure_call_time().is_some_and(|t| t.is_realtime())
+    }
+
+    pub fn platform(&self) -> Option<Platform> {
+        Platform::from_quays(self.planned_quay.as_ref(), self.estimated_quay.as_ref())
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -1108,19 +2640,31 @@ struct LegBoard {
     service_departure: ServiceDeparture,
     order: u32,
     #[serde(rename = "ExpectedDepartureOccupancy", default)]
-    expected_departure_occupancies: Vec<ExpectedDepartureOccupancy>,
+    expected_departure_occupancies: Vec<Occupancy>,
+    #[serde(default)]
+    situation_full_refs: SituationFullRefs,
+    #[serde(flatten)]
+    status: CallStatus,
 }
 
 impl LegBoard {
+    fn platform(&self) -> Option<Platform> {
+        Platform::from_quays(self.planned_quay.as_ref(), self.estimated_quay.as_ref())
+    }
+
     pub fn id(&self) -> Result<i32, OjpError> {
-        if let Ok(num) = self.stop_point_ref.parse::<i32>() {
-            Ok(num)
-        } else {
-            sloid_to_didok(&self.stop_point_ref)
-        }
+        self.stop_id()?.didok()
+    }
+
+    pub fn stop_id(&self) -> Result<StopId, OjpError> {
+        self.stop_point_ref.parse()
     }
     pub fn name(&self) -> &str {
-        self.stop_point_name.text.as_str()
+        self.stop_point_name.text()
+    }
+
+    pub fn name_text(&self) -> &Text {
+        &self.stop_point_name
     }
 }
 
@@ -1134,18 +2678,30 @@ struct LegAlight {
     estimated_quay: Option<Text>,
     service_arrival: ServiceArrival,
     order: u32,
+    #[serde(default)]
+    situation_full_refs: SituationFullRefs,
+    #[serde(flatten)]
+    status: CallStatus,
 }
 
 impl LegAlight {
+    fn platform(&self) -> Option<Platform> {
+        Platform::from_quays(self.planned_quay.as_ref(), self.estimated_quay.as_ref())
+    }
+
     pub fn id(&self) -> Result<i32, OjpError> {
-        if let Ok(num) = self.stop_point_ref.parse::<i32>() {
-            Ok(num)
-        } else {
-            sloid_to_didok(&self.stop_point_ref)
-        }
+        self.stop_id()?.didok()
+    }
+
+    pub fn stop_id(&self) -> Result<StopId, OjpError> {
+        self.stop_point_ref.parse()
     }
     pub fn name(&self) -> &str {
-        self.stop_point_name.text.as_str()
+        self.stop_point_name.text()
+    }
+
+    pub fn name_text(&self) -> &Text {
+        &self.stop_point_name
     }
 }
 
@@ -1156,6 +2712,12 @@ struct ServiceDeparture {
     estimated_time: Option<DateTime<Utc>>,
 }
 
+impl ServiceDeparture {
+    fn call_time(&self) -> CallTime {
+        CallTime::new(self.timetabled_time, self.estimated_time)
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct ServiceArrival {
@@ -1163,6 +2725,170 @@ struct ServiceArrival {
     estimated_time: Option<DateTime<Utc>>,
 }
 
+impl ServiceArrival {
+    fn call_time(&self) -> CallTime {
+        CallTime::new(self.timetabled_time, self.estimated_time)
+    }
+}
+
+/// Timetabled time of a departure or arrival and its realtime estimate, if the service sent one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallTime {
+    timetabled: DateTime<Utc>,
+    estimated: Option<DateTime<Utc>>,
+}
+
+impl CallTime {
+    pub fn new(timetabled: DateTime<Utc>, estimated: Option<DateTime<Utc>>) -> Self {
+        CallTime {
+            timetabled,
+            estimated,
+        }
+    }
+
+    pub fn timetabled(&self) -> DateTime<Utc> {
+        self.timetabled
+    }
+
+    pub fn estimated(&self) -> Option<DateTime<Utc>> {
+        self.estimated
+    }
+
+    /// Estimated time if there is one, timetabled time otherwise
+    pub fn time(&self) -> DateTime<Utc> {
+        self.estimated.unwrap_or(self.timetabled)
+    }
+
+    /// Estimated minus timetabled time, negative if early. `None` without realtime data.
+    pub fn delay(&self) -> Option<TimeDelta> {
+        Some(self.estimated? - self.timetabled)
+    }
+
+    pub fn is_realtime(&self) -> bool {
+        self.estimated.is_some()
+    }
+}
+
+/// Planned platform (quay) of a call and the one announced in realtime, if the service sent one
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Platform {
+    planned: Option<String>,
+    estimated: Option<String>,
+}
+
+impl Platform {
+    pub fn new(planned: &str) -> Self {
+        Platform {
+            planned: Some(planned.to_string()),
+            estimated: None,
+        }
+    }
+
+    pub fn with_estimated(mut self, estimated: &str) -> Self {
+        self.estimated = Some(estimated.to_string());
+        self
+    }
+
+    fn from_quays(planned: Option<&Text>, estimated: Option<&Text>) -> Option<Self> {
+        let name = |quay: Option<&Text>| {
+            quay.map(|q| q.text().trim())
+                .filter(|q| !q.is_empty())
+                .map(str::to_string)
+        };
+        let platform = Platform {
+            planned: name(planned),
+            estimated: name(estimated),
+        };
+        if platform.planned.is_none() && platform.estimated.is_none() {
+            None
+        } else {
+            Some(platform)
+        }
+    }
+
+    pub fn planned(&self) -> Option<&str> {
+        self.planned.as_deref()
+    }
+
+    pub fn estimated(&self) -> Option<&str> {
+        self.estimated.as_deref()
+    }
+
+    /// Platform to show: the estimated one if there is one, the planned one otherwise
+    pub fn name(&self) -> &str {
+        self.estimated
+            .as_deref()
+            .or(self.planned.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Whether the estimated platform differs from the planned one
+    pub fn is_changed(&self) -> bool {
+        match (&self.planned, &self.estimated) {
+            (Some(planned), Some(estimated)) => planned != estimated,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Realtime status of a call at a stop, read from the `UnplannedStop`, `NotServicedStop`,
+/// `NoBoardingAtStop` and `NoAlightingAtStop` flags of the call
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CallStatus {
+    #[serde(rename = "UnplannedStop", default, deserialize_with = "flag")]
+    unplanned: bool,
+    #[serde(rename = "NotServicedStop", default, deserialize_with = "flag")]
+    not_serviced: bool,
+    #[serde(rename = "NoBoardingAtStop", default, deserialize_with = "flag")]
+    no_boarding: bool,
+    #[serde(rename = "NoAlightingAtStop", default, deserialize_with = "flag")]
+    no_alighting: bool,
+}
+
+/// Boolean element of a flattened struct: quick-xml hands it over as an element with a text
+fn flag<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Flag {
+        #[serde(rename = "$text")]
+        text: String,
+    }
+    Flag::deserialize(deserializer)?
+        .text
+        .trim()
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+impl CallStatus {
+    /// Whether the stop was added to the timetabled ones
+    pub fn is_unplanned(&self) -> bool {
+        self.unplanned
+    }
+
+    /// Whether the service skips the stop although it is in the timetable
+    pub fn is_not_serviced(&self) -> bool {
+        self.not_serviced
+    }
+
+    pub fn can_board(&self) -> bool {
+        !self.not_serviced && !self.no_boarding
+    }
+
+    pub fn can_alight(&self) -> bool {
+        !self.not_serviced && !self.no_alighting
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct Service {
@@ -1183,21 +2909,178 @@ struct Service {
     destination_text: Text,
     #[serde(default)]
     origin_stop_point_ref: String,
+    #[serde(default)]
+    situation_full_refs: SituationFullRefs,
+    #[serde(default)]
+    unplanned: bool,
+    #[serde(default)]
+    cancelled: bool,
+    #[serde(default)]
+    deviation: bool,
+}
+
+/// Public view of the service (line, operator, train number, ...) operating a timed leg or
+/// stop event
+#[derive(Debug, Clone, Copy)]
+pub struct ServiceInfo<'a> {
+    service: &'a Service,
+}
+
+impl<'a> ServiceInfo<'a> {
+    /// Line number as shown to passengers, e.g. "S4" or "IR65"
+    pub fn public_code(&self) -> &'a str {
+        &self.service.public_code
+    }
+
+    pub fn line_ref(&self) -> &'a str {
+        &self.service.line_ref
+    }
+
+    pub fn direction_ref(&self) -> &'a str {
+        &self.service.direction_ref
+    }
+
+    pub fn journey_ref(&self) -> &'a str {
+        &self.service.journey_ref
+    }
+
+    pub fn operating_day_ref(&self) -> &'a str {
+        &self.service.operating_day_ref
+    }
+
+    pub fn published_service_name(&self) -> &'a str {
+        self.service.published_service_name.text()
+    }
+
+    /// Label of the line for display, e.g. "IC 8" or "S3": the published service name,
+    /// or the public code when the former is empty
+    pub fn line_label(&self) -> &'a str {
+        let name = self.published_service_name().trim();
+        if name.is_empty() {
+            self.public_code()
+        } else {
+            name
+        }
+    }
+
+    pub fn train_number(&self) -> &'a str {
+        &self.service.train_number
+    }
+
+    pub fn operator_ref(&self) -> &'a str {
+        &self.service.operator_ref
+    }
+
+    /// Localized name of the mode, e.g. "Zug" or "Bus"
+    pub fn mode_name(&self) -> &'a str {
+        self.service.mode.name()
+    }
+
+    /// Name of the mode in all languages sent by the service
+    pub fn mode_name_text(&self) -> Option<&'a Text> {
+        self.service.mode.name.as_ref()
+    }
+
+    pub fn transport_mode(&self) -> TransportMode {
+        self.service.mode.transport_mode()
+    }
+
+    /// Heading of the service, e.g. "Zürich HB"
+    pub fn destination(&self) -> &'a str {
+        self.service.destination_text.text()
+    }
+
+    /// Heading of the service in all languages sent by the service
+    pub fn destination_text(&self) -> &'a Text {
+        &self.service.destination_text
+    }
+
+    pub fn destination_stop_point_ref(&self) -> &'a str {
+        &self.service.destination_stop_point_ref
+    }
+
+    pub fn origin(&self) -> &'a str {
+        self.service.origin_text.text()
+    }
+
+    /// Name of the product category, e.g. "InterRegio"
+    pub fn product_category(&self) -> Option<&'a str> {
+        Some(self.service.product_category.as_ref()?.name.text())
+    }
+
+    /// Short name of the product category, e.g. "IR"
+    pub fn product_category_short_name(&self) -> Option<&'a str> {
+        Some(self.service.product_category.as_ref()?.short_name.text())
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.service.cancelled
+    }
+
+    /// Whether the service is not in the timetable, e.g. an extra train
+    pub fn is_unplanned(&self) -> bool {
+        self.service.unplanned
+    }
+
+    /// Whether the service deviates from its timetabled route
+    pub fn has_deviation(&self) -> bool {
+        self.service.deviation
+    }
+
+    /// Situations affecting the whole service
+    pub fn situation_refs(&self) -> &'a [SituationRef] {
+        &self.service.situation_full_refs.refs
+    }
+}
+
+impl<'a> From<&'a Service> for ServiceInfo<'a> {
+    fn from(service: &'a Service) -> Self {
+        ServiceInfo { service }
+    }
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct Mode {
-    pt_mode: String,
-    #[serde(default)]
-    rail_submode: String,
-    name: Text,
-    short_name: Text,
+    pt_mode: PtMode,
+    rail_submode: Option<RailSubmode>,
+    coach_submode: Option<CoachSubmode>,
+    metro_submode: Option<MetroSubmode>,
+    bus_submode: Option<BusSubmode>,
+    tram_submode: Option<TramSubmode>,
+    water_submode: Option<WaterSubmode>,
+    air_submode: Option<AirSubmode>,
+    telecabin_submode: Option<TelecabinSubmode>,
+    funicular_submode: Option<FunicularSubmode>,
+    taxi_submode: Option<TaxiSubmode>,
+    snow_and_ice_submode: Option<SnowAndIceSubmode>,
+    name: Option<Text>,
+    short_name: Option<Text>,
 }
 
 impl Mode {
+    /// Localized name, only sent for the modes of services
     pub fn name(&self) -> &str {
-        self.name.text.as_str()
+        self.name.as_ref().map_or("", |n| n.text())
+    }
+
+    pub fn transport_mode(&self) -> TransportMode {
+        // OJP sends at most one submode
+        let submode = self
+            .rail_submode
+            .clone()
+            .map(Submode::Rail)
+            .or_else(|| self.coach_submode.clone().map(Submode::Coach))
+            .or_else(|| self.metro_submode.clone().map(Submode::Metro))
+            .or_else(|| self.bus_submode.clone().map(Submode::Bus))
+            .or_else(|| self.tram_submode.clone().map(Submode::Tram))
+            .or_else(|| self.water_submode.clone().map(Submode::Water))
+            .or_else(|| self.air_submode.clone().map(Submode::Air))
+            .or_else(|| self.telecabin_submode.clone().map(Submode::Telecabin))
+            .or_else(|| self.funicular_submode.clone().map(Submode::Funicular))
+            .or_else(|| self.taxi_submode.clone().map(Submode::Taxi))
+            .or_else(|| self.snow_and_ice_submode.clone().map(Submode::SnowAndIce));
+        TransportMode::new(self.pt_mode.clone(), submode)
     }
 }
 
@@ -1217,29 +3100,42 @@ struct Attribute {
     importance: u32,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct Text {
-    text: String,
-}
-
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct EmissionCO2 {
-    kilogram_per_person_km: f32,
+    kilogram_per_person_km: f64,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
-struct ExpectedDepartureOccupancy {
-    fare_class: String,
-    occupancy_level: String,
+struct LegTrack {
+    #[serde(rename = "TrackSection", default)]
+    track_sections: Vec<TrackSection>,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct LegTrack {
-    track_section: TrackSection,
+impl LegTrack {
+    /// Length in meters, if all sections have one
+    fn length(&self) -> Option<u32> {
+        self.track_sections.iter().map(|s| s.length).sum()
+    }
+
+    /// Positions of the projections of all sections, without the repeated point where two
+    /// sections meet
+    fn positions(&self) -> Vec<Coordinate> {
+        let mut positions: Vec<Coordinate> = Vec::new();
+        for position in self
+            .track_sections
+            .iter()
+            .filter_map(|s| s.link_projection.as_ref())
+            .flat_map(|p| &p.positions)
+        {
+            let coordinate = Coordinate::new(position.longitude, position.latitude);
+            if positions.last() != Some(&coordinate) {
+                positions.push(coordinate);
+            }
+        }
+        positions
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -1251,13 +3147,14 @@ struct TrackSection {
     road_name: Option<String>,
     #[serde(with = "duration")]
     duration: Duration,
-    length: i32,
+    length: Option<u32>,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct TrackSectionEndpoint {
-    stop_point_ref: String,
+    stop_point_ref: Option<String>,
+    geo_position: Option<GeoPosition>,
     name: Text,
 }
 
@@ -1280,12 +3177,14 @@ struct Position {
 struct OJPLocationInformationDelivery {
     #[serde(rename = "PlaceResult", default)]
     place_results: Vec<PlaceResult>,
+    error_condition: Option<ErrorCondition>,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct StopEventResponseContext {
     places: Places,
+    situations: Option<Situations>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -1294,15 +3193,120 @@ struct OJPStopEventDelivery {
     stop_event_response_context: Option<StopEventResponseContext>,
     #[serde(rename = "StopEventResult", default)]
     stop_event_results: Vec<StopEventResult>,
+    error_condition: Option<ErrorCondition>,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
-struct StopEventResult {
+pub struct StopEventResult {
     id: String,
     stop_event: StopEvent,
 }
 
+impl StopEventResult {
+    pub fn stop_point_ref(&self) -> &str {
+        &self.stop_event.this_call.call_at_stop.stop_point_ref
+    }
+
+    pub fn stop_name(&self) -> &str {
+        self.stop_event
+            .this_call
+            .call_at_stop
+            .stop_point_name
+            .text()
+    }
+
+    pub fn stop_name_text(&self) -> &Text {
+        &self.stop_event.this_call.call_at_stop.stop_point_name
+    }
+
+    pub fn departure_time(&self) -> Option<NaiveDateTime> {
+        Some(self.departure_call_time()?.time().naive_utc())
+    }
+
+    pub fn arrival_time(&self) -> Option<NaiveDateTime> {
+        Some(self.arrival_call_time()?.time().naive_utc())
+    }
+
+    pub fn departure_call_time(&self) -> Option<CallTime> {
+        let call = &self.stop_event.this_call.call_at_stop;
+        Some(call.service_departure.as_ref()?.call_time())
+    }
+
+    pub fn arrival_call_time(&self) -> Option<CallTime> {
+        let call = &self.stop_event.this_call.call_at_stop;
+        Some(call.service_arrival.as_ref()?.call_time())
+    }
+
+    pub fn departure_delay(&self) -> Option<TimeDelta> {
+        self.departure_call_time()?.delay()
+    }
+
+    pub fn platform(&self) -> Option<Platform> {
+        let call = &self.stop_event.this_call.call_at_stop;
+        Platform::from_quays(call.planned_quay.as_ref(), call.estimated_quay.as_ref())
+    }
+
+    pub fn platform_changed(&self) -> bool {
+        self.platform().is_some_and(|p| p.is_changed())
+    }
+
+    pub fn status(&self) -> CallStatus {
+        self.stop_event.this_call.call_at_stop.status
+    }
+
+    /// Expected occupancy per fare class when the service departs from the stop
+    pub fn occupancies(&self) -> &[Occupancy] {
+        &self
+            .stop_event
+            .this_call
+            .call_at_stop
+            .expected_departure_occupancies
+    }
+
+    pub fn occupancy(&self, fare_class: &FareClass) -> Option<&OccupancyLevel> {
+        occupancy::level_of(self.occupancies(), fare_class)
+    }
+
+    /// Whether the service is cancelled or skips the stop
+    pub fn is_cancelled(&self) -> bool {
+        self.stop_event.service.cancelled || self.status().is_not_serviced()
+    }
+
+    pub fn mode(&self) -> &str {
+        self.stop_event.service.mode.name()
+    }
+
+    pub fn transport_mode(&self) -> TransportMode {
+        self.stop_event.service.mode.transport_mode()
+    }
+
+    pub fn destination(&self) -> &str {
+        self.stop_event.service.destination_text.text()
+    }
+
+    pub fn destination_text(&self) -> &Text {
+        &self.stop_event.service.destination_text
+    }
+
+    pub fn service(&self) -> ServiceInfo<'_> {
+        ServiceInfo::from(&self.stop_event.service)
+    }
+
+    /// Situations affecting the service or the stop, without duplicates
+    pub fn situation_refs(&self) -> Vec<&SituationRef> {
+        let stop_event = &self.stop_event;
+        situation::dedup(
+            stop_event
+                .service
+                .situation_full_refs
+                .refs
+                .iter()
+                .chain(&stop_event.this_call.call_at_stop.situation_full_refs.refs),
+        )
+    }
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct StopEvent {
@@ -1330,9 +3334,17 @@ struct ThisCall {
 struct CallAtStop {
     stop_point_ref: String,
     stop_point_name: Text,
+    planned_quay: Option<Text>,
+    estimated_quay: Option<Text>,
     service_departure: Option<ServiceDeparture>,
     service_arrival: Option<ServiceArrival>,
     order: u32,
+    #[serde(rename = "ExpectedDepartureOccupancy", default)]
+    expected_departure_occupancies: Vec<Occupancy>,
+    #[serde(default)]
+    situation_full_refs: SituationFullRefs,
+    #[serde(flatten)]
+    status: CallStatus,
 }
 
 #[derive(Deserialize, Debug)]
@@ -1344,12 +3356,36 @@ pub struct PlaceResult {
 }
 
 impl PlaceResult {
+    /// DIDOK number of the stop place, `None` for other places
     pub fn stop_place_ref(&self) -> Option<i32> {
-        Some(self.place.stop_place.as_ref()?.stop_place_ref)
+        self.stop_id()?.didok().ok()
+    }
+
+    /// Id of the stop place or stop point, `None` for other places
+    pub fn stop_id(&self) -> Option<StopId> {
+        let stop_ref = match (&self.place.stop_place, &self.place.stop_point) {
+            (Some(stop_place), _) => &stop_place.stop_place_ref,
+            (None, Some(stop_point)) => &stop_point.stop_point_ref,
+            (None, None) => return None,
+        };
+        stop_ref.parse().ok()
+    }
+
+    /// Modes serving the place
+    pub fn modes(&self) -> Vec<TransportMode> {
+        self.place
+            .place_modes
+            .iter()
+            .map(Mode::transport_mode)
+            .collect()
     }
 
     pub fn stop_place_name(&self) -> Option<&str> {
-        Some(&self.place.stop_place.as_ref()?.stop_place_name.text)
+        Some(self.place.stop_place.as_ref()?.stop_place_name.text())
+    }
+
+    pub fn stop_place_name_text(&self) -> Option<&Text> {
+        Some(&self.place.stop_place.as_ref()?.stop_place_name)
     }
 }
 
@@ -1362,7 +3398,7 @@ struct Place {
     name: Text,
     geo_position: GeoPosition,
     #[serde(rename = "Mode", default)]
-    place_modes: Vec<PlaceMode>,
+    place_modes: Vec<Mode>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -1385,7 +3421,7 @@ struct TopographicPlace {
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct StopPlace {
-    stop_place_ref: i32,
+    stop_place_ref: String,
     stop_place_name: Text,
     private_code: PrivateCode,
     topographic_place_ref: String,
@@ -1405,24 +3441,18 @@ struct GeoPosition {
     latitude: f64,
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "PascalCase")]
-struct PlaceMode {
-    pt_mode: String,
-    #[serde(default)]
-    rail_submode: String,
-    #[serde(default)]
-    tram_submode: String,
-    #[serde(default)]
-    bus_submode: String,
-    #[serde(default)]
-    funicular_submode: String,
-}
-
 #[cfg(test)]
 mod test {
-    use crate::{OJP, RequestBuilder, RequestType, SimplifiedTrip, token};
-    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use crate::{
+        BusSubmode, Coordinate, ErrorKind, FareClass, LegType, LocationId, OJP, OccupancyLevel,
+        OjpError, Platform, ProblemType, PtMode, RailSubmode, SimplifiedLeg, SimplifiedTrip,
+        StopId, Submode, TramSubmode, TransportMode,
+    };
+    #[cfg(feature = "client")]
+    use crate::{RequestBuilder, RequestType, token};
+    use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+    #[cfg(feature = "client")]
+    use chrono::{NaiveDate, NaiveTime};
     use std::error::Error;
     use test_log::test;
 
@@ -1449,6 +3479,18 @@ mod test {
         let ojp = parse_xml("test_xml/location_simple.xml").unwrap();
         let place_results = ojp.place_results().unwrap();
         assert_eq!(place_results.len(), 14);
+        assert_eq!(
+            place_results[1].modes(),
+            vec![
+                TransportMode::new(PtMode::Tram, Some(Submode::Tram(TramSubmode::CityTram))),
+                TransportMode::new(PtMode::Bus, Some(Submode::Bus(BusSubmode::LocalBus))),
+            ]
+        );
+        let stop_id = place_results[0].stop_id().unwrap();
+        assert_eq!(
+            Some(stop_id.didok().unwrap()),
+            place_results[0].stop_place_ref()
+        );
     }
 
     #[test]
@@ -1458,7 +3500,22 @@ mod test {
 
     #[test]
     fn stop_simple() {
-        let _ojp = parse_xml("test_xml/stop_simple.xml").unwrap();
+        let ojp = parse_xml("test_xml/stop_simple.xml").unwrap();
+        let stop_events = ojp.stop_events().unwrap();
+        let first = stop_events.first().unwrap();
+        assert_eq!(first.stop_name(), "Bern");
+        assert_eq!(first.destination(), "Biel/Bienne");
+        assert_eq!(first.stop_name_text().lang(), Some("de"));
+        assert_eq!(first.destination_text().best(&["fr", "de"]), "Biel/Bienne");
+        assert_eq!(
+            first.service().mode_name_text().unwrap().get("de"),
+            Some("Zug")
+        );
+        assert_eq!(
+            first.departure_time().unwrap(),
+            NaiveDateTime::parse_from_str("2025-10-17T08:42:12Z", FORMAT).unwrap()
+        );
+        assert!(first.arrival_time().is_none());
     }
 
     #[test]
@@ -1498,6 +3555,30 @@ mod test {
             simplified_trip.arrival_time(),
             NaiveDateTime::parse_from_str("2025-10-17T09:10:54Z", FORMAT).unwrap()
         );
+        assert_eq!(simplified_trip.trip_info(), trip_after.trip_info());
+        let leg = simplified_trip.legs()[0];
+        assert_eq!(leg.departure_id(), simplified_trip.departure_id());
+        assert_eq!(leg.duration().num_seconds(), 3 * 60 + 30);
+        assert_eq!(leg.line(), Some("S4"));
+        assert_eq!(leg.pt_mode(), Some(&PtMode::Rail));
+        assert_eq!(
+            leg.transport_mode().unwrap().submode(),
+            Some(&Submode::Rail(RailSubmode::RegionalRail))
+        );
+        assert_eq!(leg.destination(), Some("Zürich HB"));
+
+        let LegType::Timed(timed_leg) = LegType::try_from(trip_after.legs()[0]).unwrap() else {
+            panic!("expected a timed leg");
+        };
+        let departure = timed_leg.departure_stop_id().unwrap();
+        assert_eq!(departure.to_string(), "ch:1:sloid:3091:0:185066");
+        assert_eq!(departure.didok().unwrap(), 8503091);
+        let service = timed_leg.service();
+        assert_eq!(service.public_code(), "S4");
+        assert_eq!(service.train_number(), "12506");
+        assert_eq!(service.operator_ref(), "78");
+        assert_eq!(service.product_category(), Some("Sihltalbahn"));
+        assert_eq!(service.origin(), "n/a");
 
         let trips = ojp.trips().unwrap();
         assert_eq!(trips.len(), 3);
@@ -1511,11 +3592,653 @@ mod test {
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn simplified_trip_json_roundtrip() {
+        let ojp = parse_xml("test_xml/trip_lots.xml").unwrap();
+        let trip = SimplifiedTrip::try_from(ojp.fastest_trip().unwrap()).unwrap();
+        let json = serde_json::to_string(&trip).unwrap();
+        assert_eq!(serde_json::from_str::<SimplifiedTrip>(&json).unwrap(), trip);
+        assert!(serde_json::from_str::<SimplifiedTrip>(r#"{"legs":[]}"#).is_err());
+
+        let info = trip.trip_info();
+        let json = serde_json::to_value(info).unwrap();
+        assert_eq!(json["duration"], info.duration().num_seconds());
+        assert_eq!(
+            serde_json::from_value::<crate::TripInfo>(json).unwrap(),
+            info
+        );
+    }
+
+    #[test]
+    fn simplified_trips_are_contiguous() {
+        for xml in ["test_xml/trip_simple.xml", "test_xml/trip_lots.xml"] {
+            let ojp = parse_xml(xml).unwrap();
+            for trip in ojp.trips().unwrap() {
+                let simplified_trip = SimplifiedTrip::try_from(trip.trip()).unwrap();
+                simplified_trip.validate().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn trip_from_coordinate_to_address() {
+        let ojp = parse_xml("test_xml/trip_address.xml").unwrap();
+        let trip = ojp.trips().unwrap()[0].trip();
+        let simplified_trip = SimplifiedTrip::try_from(trip).unwrap();
+        simplified_trip.validate().unwrap();
+        assert_eq!(simplified_trip.number_of_legs(), 3);
+        assert_eq!(
+            simplified_trip.departure(),
+            &LocationId::GeoPosition {
+                longitude: 8.51931,
+                latitude: 47.36443
+            }
+        );
+        assert_eq!(simplified_trip.departure_id(), None);
+        assert_eq!(
+            simplified_trip.arrival(),
+            &LocationId::Address("ch:1:address:8001:bahnhofstrasse:1".to_string())
+        );
+        let timed_leg = simplified_trip.legs()[1];
+        assert_eq!(timed_leg.departure_id(), Some(8503091));
+        assert_eq!(timed_leg.arrival_id(), Some(8503088));
+        let quay = timed_leg.arrival_stop_id().unwrap();
+        assert_eq!(quay.to_string(), "ch:1:sloid:3088:0:99992");
+        assert!(timed_leg.arrival().same_place(&LocationId::from(8503088)));
+        assert_ne!(timed_leg.arrival(), &LocationId::from(8503088));
+
+        let last_leg = LegType::try_from(trip.legs()[2]).unwrap();
+        assert!(matches!(
+            last_leg.arrival_id(),
+            Err(OjpError::NotAStop(LocationId::Address(_)))
+        ));
+    }
+
+    #[test]
+    fn leg_delays() {
+        let ojp = parse_xml("test_xml/trip_address.xml").unwrap();
+        let trip = ojp.trip(0).unwrap();
+        assert!(trip.has_realtime());
+        assert_eq!(trip.departure_delay(), Some(TimeDelta::seconds(48)));
+        assert_eq!(trip.arrival_delay(), Some(TimeDelta::seconds(30)));
+
+        let leg = LegType::try_from(trip.legs()[1]).unwrap();
+        let departure = leg.departure_call_time().unwrap();
+        assert_eq!(
+            departure.timetabled(),
+            "2025-10-17T08:47:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(departure.time(), departure.estimated().unwrap());
+        assert_eq!(leg.delay(), Some(TimeDelta::seconds(30)));
+        let walk = LegType::try_from(trip.legs()[0]).unwrap();
+        assert!(walk.departure_call_time().is_none());
+        assert!(!walk.has_realtime());
+
+        let simplified_trip = SimplifiedTrip::try_from(trip).unwrap();
+        assert!(simplified_trip.has_realtime());
+        assert_eq!(
+            simplified_trip.departure_delay(),
+            Some(TimeDelta::seconds(48))
+        );
+        assert_eq!(
+            simplified_trip.arrival_delay(),
+            Some(TimeDelta::seconds(30))
+        );
+        let timed_leg = simplified_trip.legs()[1];
+        assert_eq!(
+            timed_leg.timetabled_departure_time(),
+            departure.timetabled().naive_utc()
+        );
+        assert_eq!(timed_leg.departure_delay(), Some(TimeDelta::seconds(48)));
+        let last_leg = simplified_trip.legs()[2];
+        assert_eq!(last_leg.arrival_delay(), None);
+        assert_eq!(last_leg.timetabled_arrival_time(), last_leg.arrival_time());
+    }
+
+    #[test]
+    fn delays_of_the_first_and_last_timed_legs() {
+        let time = |t| NaiveDateTime::parse_from_str(t, FORMAT).unwrap();
+        let leg = |from, to, dep, arr| {
+            SimplifiedLeg::new(from, "", to, "", time(dep), time(arr), "Zug".to_string())
+                .with_transport_mode(TransportMode::new(PtMode::Rail, None))
+        };
+        let trip = SimplifiedTrip::new(vec![
+            leg(1, 2, "2025-10-17T09:00:00Z", "2025-10-17T09:10:00Z"),
+            leg(2, 3, "2025-10-17T09:15:00Z", "2025-10-17T09:30:00Z")
+                .with_timetabled_times(time("2025-10-17T09:13:00Z"), time("2025-10-17T09:28:00Z")),
+            leg(3, 4, "2025-10-17T09:35:00Z", "2025-10-17T09:40:00Z"),
+        ])
+        .unwrap();
+        assert!(trip.has_realtime());
+        assert_eq!(trip.departure_delay(), None);
+        assert_eq!(trip.arrival_delay(), None);
+    }
+
+    #[test]
+    fn platform_changes() {
+        let ojp = parse_xml("test_xml/trip_situation.xml").unwrap();
+        let leg = LegType::try_from(ojp.trip(0).unwrap().legs()[0]).unwrap();
+        let departure = leg.departure_platform().unwrap();
+        assert_eq!(departure, Platform::new("1").with_estimated("1"));
+        assert!(!departure.is_changed());
+        let arrival = leg.arrival_platform().unwrap();
+        assert_eq!(arrival.planned(), Some("21"));
+        assert_eq!(arrival.to_string(), "23");
+        assert!(arrival.is_changed());
+        assert!(leg.platform_changed());
+
+        let simplified_trip = SimplifiedTrip::try_from(ojp.trip(0).unwrap()).unwrap();
+        let simplified_leg = simplified_trip.legs()[0];
+        assert_eq!(simplified_leg.arrival_platform(), Some(&arrival));
+        assert!(simplified_leg.platform_changed());
+
+        let ojp = parse_xml("test_xml/trip_address.xml").unwrap();
+        let trip = ojp.trip(0).unwrap();
+        assert!(
+            !LegType::try_from(trip.legs()[1])
+                .unwrap()
+                .platform_changed()
+        );
+        assert!(
+            LegType::try_from(trip.legs()[0])
+                .unwrap()
+                .departure_platform()
+                .is_none()
+        );
+
+        let ojp = parse_xml("test_xml/stop_simple.xml").unwrap();
+        let platform = ojp.stop_events().unwrap()[0].platform().unwrap();
+        assert_eq!(platform.name(), "49");
+        assert_eq!(platform.estimated(), None);
+        assert!(!platform.is_changed());
+    }
+
+    #[test]
+    fn cancelled_trips() {
+        let ojp = parse_xml("test_xml/trip_cancelled.xml").unwrap();
+        let cancelled = ojp.trip(0).unwrap();
+        assert!(cancelled.has_cancelled_legs());
+        assert!(!cancelled.is_feasible());
+        let leg = LegType::try_from(cancelled.legs()[0]).unwrap();
+        assert!(leg.is_cancelled());
+        assert!(leg.service().unwrap().is_cancelled());
+        let LegType::Timed(timed_leg) = leg else {
+            panic!("expected a timed leg");
+        };
+        assert!(timed_leg.departure_status().is_not_serviced());
+        assert!(!timed_leg.departure_status().can_board());
+        assert!(timed_leg.intermediates()[0].status().is_not_serviced());
+
+        assert!(cancelled.is_cancelled());
+
+        let deviated = ojp.trip(1).unwrap();
+        assert!(!deviated.has_cancelled_legs());
+        assert!(deviated.is_feasible());
+        assert!(deviated.has_deviation());
+        assert!(deviated.is_delayed());
+        assert!(!deviated.is_unplanned());
+        let leg = LegType::try_from(deviated.legs()[0]).unwrap();
+        assert!(leg.has_deviation());
+        let service = leg.service().unwrap();
+        assert!(service.has_deviation());
+        assert!(!service.is_unplanned());
+        let LegType::Timed(timed_leg) = leg else {
+            panic!("expected a timed leg");
+        };
+        assert!(timed_leg.intermediates()[0].status().is_unplanned());
+        assert!(timed_leg.arrival_status().can_alight());
+
+        let infeasible = ojp.trip(2).unwrap();
+        assert!(!infeasible.has_cancelled_legs());
+        assert!(!infeasible.is_cancelled());
+        assert!(!infeasible.is_feasible());
+
+        assert_eq!(ojp.feasible_trips().unwrap().len(), 1);
+        assert_eq!(
+            ojp.fastest_feasible_trip().unwrap().departure_time(),
+            deviated.departure_time()
+        );
+
+        let ojp = parse_xml("test_xml/trip_simple.xml").unwrap();
+        assert_eq!(
+            ojp.feasible_trips().unwrap().len(),
+            ojp.trips().unwrap().len()
+        );
+        let ojp = parse_xml("test_xml/stop_simple.xml").unwrap();
+        assert!(ojp.stop_events().unwrap().iter().all(|e| !e.is_cancelled()));
+    }
+
+    #[test]
+    fn intermediate_stops() {
+        let ojp = parse_xml("test_xml/trip_cancelled.xml").unwrap();
+        let trip = ojp.trip(1).unwrap();
+        let leg = LegType::try_from(trip.legs()[0]).unwrap();
+        let intermediates = leg.intermediates();
+        assert_eq!(intermediates.len(), 1);
+        let selnau = intermediates[0];
+        assert_eq!(selnau.name(), "Zürich Selnau");
+        assert_eq!(selnau.id().unwrap(), 8503090);
+        assert_eq!(selnau.order(), 2);
+        assert_eq!(selnau.platform().unwrap().name(), "2");
+        assert_eq!(
+            selnau.arrival_time(),
+            Some("2025-10-17T09:04:00Z".parse().unwrap())
+        );
+        assert!(!selnau.has_realtime());
+
+        let simplified_trip = SimplifiedTrip::try_from(trip).unwrap();
+        let calls = simplified_trip.legs()[0].intermediates();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].stop_id(), Some(8503090));
+        assert_eq!(calls[0].stop_name(), "Zürich Selnau");
+        assert_eq!(
+            calls[0].departure_time(),
+            selnau.departure_time().map(|t| t.naive_utc())
+        );
+        assert_eq!(calls[0].platform(), Some(&Platform::new("2")));
+
+        let ojp = parse_xml("test_xml/trip_simple.xml").unwrap();
+        let simplified_trip = SimplifiedTrip::try_from(ojp.trip(0).unwrap()).unwrap();
+        assert!(
+            simplified_trip
+                .legs()
+                .iter()
+                .all(|l| l.intermediates().is_empty())
+        );
+    }
+
+    #[test]
+    fn occupancy_forecasts() {
+        let ojp = parse_xml("test_xml/trip_occupancy.xml").unwrap();
+        let crowded = ojp.trip(0).unwrap();
+        let leg = LegType::try_from(crowded.legs()[0]).unwrap();
+        let LegType::Timed(timed_leg) = leg else {
+            panic!("expected a timed leg");
+        };
+        assert_eq!(timed_leg.occupancies().len(), 2);
+        assert_eq!(
+            timed_leg.occupancies()[1].level(),
+            &OccupancyLevel::FewSeatsAvailable
+        );
+        assert_eq!(
+            timed_leg.intermediates()[0].occupancy(&FareClass::SecondClass),
+            Some(&OccupancyLevel::StandingRoomOnly)
+        );
+        assert_eq!(
+            crowded.worst_occupancy(&FareClass::SecondClass),
+            Some(&OccupancyLevel::StandingRoomOnly)
+        );
+        assert_eq!(
+            crowded.worst_occupancy(&FareClass::FirstClass),
+            Some(&OccupancyLevel::FewSeatsAvailable)
+        );
+
+        let second_class = FareClass::SecondClass;
+        let trips = ojp
+            .trips_with_occupancy_at_most(&second_class, &OccupancyLevel::FewSeatsAvailable)
+            .unwrap();
+        assert_eq!(trips.len(), 1);
+        assert_eq!(
+            trips[0].trip().departure_time(),
+            ojp.trip(1).unwrap().departure_time()
+        );
+        assert!(
+            ojp.trips_with_occupancy_at_most(&second_class, &OccupancyLevel::Empty)
+                .unwrap()
+                .is_empty()
+        );
+        let err = ojp
+            .trips_with_occupancy_at_most(&second_class, &OccupancyLevel::Unknown)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            OjpError::UnrankedOccupancyLevel(OccupancyLevel::Unknown)
+        ));
+        assert_eq!(err.kind(), ErrorKind::ClientMisuse);
+        assert!(
+            ojp.trips_with_occupancy_at_most(
+                &second_class,
+                &OccupancyLevel::from_code("overcrowded")
+            )
+            .is_err()
+        );
+        let trips = ojp.trips_by_occupancy(&second_class).unwrap();
+        assert_eq!(
+            trips[0].trip().departure_time(),
+            ojp.trip(1).unwrap().departure_time()
+        );
+
+        let ojp = parse_xml("test_xml/stop_simple.xml").unwrap();
+        let stop_event = ojp.stop_events().unwrap()[0];
+        assert_eq!(
+            stop_event.occupancy(&second_class),
+            Some(&OccupancyLevel::ManySeatsAvailable)
+        );
+        let ojp = parse_xml("test_xml/trip_simple.xml").unwrap();
+        assert_eq!(ojp.trip(0).unwrap().worst_occupancy(&second_class), None);
+    }
+
+    #[test]
+    fn co2_emissions() {
+        let ojp = parse_xml("test_xml/trip_simple.xml").unwrap();
+        let trip = ojp.trip(0).unwrap();
+        assert_eq!(trip.legs()[0].co2_factor(), Some(0.007));
+        assert_eq!(trip.legs()[0].co2(), None);
+        let co2 = trip.co2();
+        assert!(co2.is_complete());
+        assert_eq!(co2.distance(), Some(2244));
+        assert!((co2.kilograms() - 0.015708).abs() < 1e-6);
+        assert!((co2.car_kilograms(0.2).unwrap() - 0.4488).abs() < 1e-6);
+        assert!((co2.saved_compared_to_car(0.2).unwrap() - 0.433092).abs() < 1e-6);
+
+        let ojp = parse_xml("test_xml/trip_address.xml").unwrap();
+        let trip = ojp.trip(0).unwrap();
+        let walk = trip.legs()[0];
+        assert!(walk.is_emission_free());
+        assert_eq!(walk.length(), Some(320));
+        assert_eq!(walk.co2(), Some(0.0));
+        let co2 = trip.co2();
+        assert!(co2.is_complete());
+        assert!((co2.kilograms() - 0.011018).abs() < 1e-6);
+    }
+
+    #[test]
+    fn leg_geometry() {
+        let ojp = parse_xml("test_xml/trip_projection.xml").unwrap();
+        let trip = ojp.trip(0).unwrap();
+        let legs: Vec<_> = trip
+            .legs()
+            .into_iter()
+            .map(|l| LegType::try_from(l).unwrap())
+            .collect();
+        assert_eq!(legs[0].geometry().len(), 3);
+        let timed = legs[1].geometry();
+        assert_eq!(timed.len(), 5);
+        assert_eq!(timed[0], Coordinate::new(8.52181, 47.36271));
+        assert_eq!(timed[4], Coordinate::new(8.54031, 47.37807));
+        assert!(legs[2].geometry().is_empty());
+        assert_eq!(trip.legs()[1].length(), Some(1574));
+
+        let geojson: serde_json::Value = serde_json::from_str(&trip.to_geojson().unwrap()).unwrap();
+        assert_eq!(geojson["type"], "FeatureCollection");
+        let features = geojson["features"].as_array().unwrap();
+        assert_eq!(features.len(), 3);
+        assert_eq!(features[1]["geometry"]["type"], "LineString");
+        assert_eq!(
+            features[1]["geometry"]["coordinates"][2],
+            serde_json::json!([8.53119, 47.37182])
+        );
+        assert_eq!(features[1]["properties"]["line"], "S4");
+        assert_eq!(features[1]["properties"]["mode"], "Zug");
+        assert_eq!(features[1]["properties"]["pt_mode"], "rail");
+        assert_eq!(features[0]["properties"]["mode"], "foot");
+        assert!(features[2]["geometry"].is_null());
+
+        let simplified = SimplifiedTrip::try_from(trip).unwrap();
+        assert_eq!(simplified.legs()[1].geometry(), timed.as_slice());
+    }
+
+    #[test]
+    fn gpx_and_kml_export() {
+        let ojp = parse_xml("test_xml/trip_projection.xml").unwrap();
+        let trip = ojp.trip(0).unwrap();
+        let gpx = trip.to_gpx().unwrap();
+        // The walk to the address has no position, its start is where the train arrives
+        let waypoints: Vec<_> = gpx.lines().filter(|l| l.contains("<wpt ")).collect();
+        assert_eq!(
+            waypoints,
+            [
+                r#"  <wpt lat="47.36443" lon="8.51931"><time>2025-10-17T08:42:48Z</time><name>8.51931, 47.36443</name><type>departure</type></wpt>"#,
+                r#"  <wpt lat="47.36271" lon="8.52181"><time>2025-10-17T08:47:48Z</time><name>Zürich Giesshübel</name><type>transfer</type></wpt>"#,
+                r#"  <wpt lat="47.37807" lon="8.54031"><time>2025-10-17T08:51:30Z</time><name>Zürich HB</name><type>transfer</type></wpt>"#,
+            ]
+        );
+        assert_eq!(gpx.matches("<trk>").count(), 2);
+        assert_eq!(gpx.matches("<trkpt ").count(), 8);
+        assert!(gpx.contains("<trk><name>S4: Zürich Giesshübel - Zürich HB</name>"));
+
+        let kml = trip.to_kml().unwrap();
+        assert!(kml.contains("<name>8.51931, 47.36443 - Zürich, Bahnhofstrasse 1</name>"));
+        assert_eq!(kml.matches("<Point>").count(), 3);
+        assert_eq!(kml.matches("<LineString>").count(), 2);
+        assert!(kml.contains("<coordinates>8.52181,47.36271 8.52612,47.36743 8.53119,47.37182 8.53655,47.37551 8.54031,47.37807</coordinates>"));
+
+        for document in [gpx, kml] {
+            let mut reader = quick_xml::Reader::from_str(&document);
+            while reader.read_event().unwrap() != quick_xml::events::Event::Eof {}
+        }
+    }
+
+    #[test]
+    fn gpx_export_without_projection() {
+        let ojp = parse_xml("test_xml/trip_simple.xml").unwrap();
+        let trip = ojp.trip(0).unwrap();
+        assert!(!trip.to_gpx().unwrap().contains("<wpt "));
+
+        let simplified = ojp.simplified_trip(trip).unwrap();
+        let leg = &simplified.legs()[0];
+        assert_eq!(
+            leg.departure_position(),
+            Some(Coordinate::new(8.52202, 47.36261))
+        );
+        assert!(leg.geometry().is_empty());
+        let gpx = simplified.to_gpx();
+        assert_eq!(gpx.matches("<wpt ").count(), 2 * simplified.legs().len());
+        assert!(gpx.contains(r#"<wpt lat="47.36261" lon="8.52202">"#));
+        assert!(!gpx.contains("<trk>"));
+        assert_eq!(
+            simplified.to_kml().matches("<Point>").count(),
+            2 * simplified.legs().len()
+        );
+    }
+
+    #[test]
+    fn trip_situations() {
+        let ojp = parse_xml("test_xml/trip_situation.xml").unwrap();
+        let situations = ojp.situations();
+        assert_eq!(situations.len(), 2);
+
+        let construction = situations[0];
+        assert_eq!(construction.participant_ref(), "ch:1:sboid:100058");
+        assert_eq!(construction.version(), Some(2));
+        assert_eq!(construction.alert_cause(), Some("constructionWork"));
+        assert_eq!(construction.priority(), Some(3));
+        assert_eq!(construction.scope_type(), Some("line"));
+        assert_eq!(construction.source_type(), Some("directReport"));
+        assert_eq!(construction.validity_periods().len(), 2);
+        let saturday = "2025-10-18T12:00:00Z".parse().unwrap();
+        let sunday = "2025-10-19T12:00:00Z".parse().unwrap();
+        assert!(!construction.is_valid_at(saturday));
+        assert!(construction.is_valid_at(sunday));
+        let escalator = situations[1];
+        assert_eq!(escalator.validity_periods()[0].end_time(), None);
+        assert!(escalator.is_valid_at(sunday));
+        let texts = construction.texts("general");
+        assert_eq!(texts.summary().get("fr"), Some("Sihltal: travaux"));
+        assert_eq!(
+            texts.duration().text(),
+            "Samstag und Sonntag, jeweils ganztags."
+        );
+        let texts = escalator.texts("stopPoint");
+        assert_eq!(
+            texts.summary().text(),
+            "Zürich HB: Rolltreppe ausser Betrieb"
+        );
+        assert!(texts.description().is_empty());
+        assert!(escalator.texts("general").is_empty());
+
+        let trip = ojp.trip(0).unwrap();
+        let refs = trip.situation_refs();
+        assert_eq!(refs.len(), 2);
+        let leg = LegType::try_from(trip.legs()[0]).unwrap();
+        assert_eq!(leg.situation_refs(), refs);
+        assert_eq!(
+            leg.service().unwrap().situation_refs(),
+            [construction.reference()]
+        );
+        assert_eq!(
+            ojp.situation(refs[1]).map(|s| s.situation_number()),
+            Some(escalator.situation_number())
+        );
+        assert_eq!(ojp.trip_situations(trip).len(), 2);
+
+        let ojp = parse_xml("test_xml/trip_simple.xml").unwrap();
+        assert!(ojp.situations().is_empty());
+        assert!(ojp.trip(0).unwrap().situation_refs().is_empty());
+    }
+
+    #[test]
+    fn stop_event_situations() {
+        let ojp = parse_xml("test_xml/stop_situation.xml").unwrap();
+        let stop_event = ojp.stop_events().unwrap()[0];
+        let refs = stop_event.situation_refs();
+        assert_eq!(refs.len(), 1);
+        let situation = ojp.situation(refs[0]).unwrap();
+        assert_eq!(situation.alert_cause(), Some("accident"));
+        assert_eq!(situation.perspectives(), ["general", "stopPoint"]);
+
+        let general = situation.texts("general");
+        let summary = general.summary();
+        assert_eq!(
+            summary.text(),
+            "Tram 11: Unterbruch zwischen Kreuzplatz und Rehalp"
+        );
+        assert_eq!(
+            summary.languages().collect::<Vec<_>>(),
+            ["de", "fr", "it", "en"]
+        );
+        assert_eq!(
+            summary.get("it"),
+            Some("Tram 11: interruzione tra Kreuzplatz e Rehalp")
+        );
+        assert_eq!(general.reason().best(&["fr"]), "Accident");
+        let description = general.description();
+        assert_eq!(description.len(), 2);
+        assert_eq!(
+            description[0].get("en"),
+            Some("Tram line 11 is interrupted between Zürich, Kreuzplatz and Zürich, Rehalp.")
+        );
+        assert_eq!(description[1].languages().collect::<Vec<_>>(), ["de", "en"]);
+        assert_eq!(
+            description[1].best(&["fr", "en"]),
+            "The duration of the disruption is not known yet."
+        );
+        assert_eq!(
+            general.consequence()[0].text(),
+            "Zwischen Kreuzplatz und Rehalp verkehren Ersatzbusse."
+        );
+        assert_eq!(
+            general.recommendation()[0].get("fr"),
+            Some("Utilisez les bus de remplacement depuis l'arrêt Kreuzplatz.")
+        );
+        assert_eq!(
+            general.duration().get("de"),
+            Some("Bis voraussichtlich 12:00 Uhr.")
+        );
+
+        let stop_point = situation.texts("stopPoint");
+        assert_eq!(
+            stop_point.summary().get("de"),
+            Some("Ersatzbusse ab Kreuzplatz")
+        );
+        assert!(stop_point.description().is_empty());
+    }
+
+    #[test]
+    fn simplified_trip_needs_legs() {
+        assert!(matches!(
+            SimplifiedTrip::new(vec![]),
+            Err(OjpError::EmptyTrip)
+        ));
+
+        let time = |t| NaiveDateTime::parse_from_str(t, FORMAT).unwrap();
+        let leg = |from, to, dep, arr| {
+            SimplifiedLeg::new(from, "", to, "", time(dep), time(arr), "Zug".to_string())
+        };
+        let trip = SimplifiedTrip::new(vec![
+            leg(1, 2, "2025-10-17T09:00:00Z", "2025-10-17T09:10:00Z"),
+            leg(3, 4, "2025-10-17T09:15:00Z", "2025-10-17T09:30:00Z"),
+        ])
+        .unwrap();
+        assert!(matches!(
+            trip.validate(),
+            Err(OjpError::DisconnectedLegs {
+                leg: 1,
+                arrival: LocationId::Stop(StopId::Didok(2)),
+                departure: LocationId::Stop(StopId::Didok(3))
+            })
+        ));
+        let trip = SimplifiedTrip::new(vec![
+            leg(1, 2, "2025-10-17T09:00:00Z", "2025-10-17T09:10:00Z"),
+            leg(2, 4, "2025-10-17T09:05:00Z", "2025-10-17T09:30:00Z"),
+        ])
+        .unwrap();
+        assert!(matches!(
+            trip.validate(),
+            Err(OjpError::LegsOutOfOrder { leg: 1 })
+        ));
+    }
+
+    #[test]
+    fn trip_no_trip_found() {
+        let ojp = parse_xml("test_xml/trip_no_trip_found.xml").unwrap();
+        assert_eq!(ojp.error(), Some("TRIP_NOTRIPFOUND"));
+        let problem = ojp.problem().unwrap();
+        assert_eq!(problem.problem_type(), &ProblemType::TripNoTripFound);
+        assert_eq!(
+            problem.description(),
+            Some("No trip found between origin and destination")
+        );
+        assert!(ojp.trips().unwrap().is_empty());
+        let err = OjpError::TripProblem {
+            dep_id: StopId::Didok(8503091),
+            arr_id: StopId::Didok(8503000),
+            problem: Box::new(problem),
+        };
+        assert_eq!(err.kind(), ErrorKind::NoData);
+    }
+
+    #[test]
+    fn parse_error_keeps_raw_body() {
+        let body = format!("<html>{}</html>", "Bad Gateway ".repeat(50));
+        let err = OJP::try_from(body.as_str()).unwrap_err();
+        let OjpError::FailedToParseXml { body: raw, .. } = &err else {
+            panic!("unexpected error {err}");
+        };
+        assert_eq!(raw.as_str(), body);
+        assert!(raw.preview().len() < body.len());
+        assert!(!err.to_string().contains(&body));
+        assert_eq!(err.kind(), ErrorKind::ServerRejection);
+    }
+
+    #[test]
+    fn location_no_results() {
+        let ojp = parse_xml("test_xml/location_no_results.xml").unwrap();
+        let problem = ojp.problem().unwrap();
+        assert_eq!(problem.problem_type(), &ProblemType::LocationNoResults);
+        assert!(problem.problem_type().is_no_result());
+        assert_eq!(problem.description(), None);
+        assert!(ojp.place_results().unwrap().is_empty());
+    }
+
+    #[test]
+    fn no_problem_in_successful_responses() {
+        for xml in [
+            "test_xml/trip_simple.xml",
+            "test_xml/location_simple.xml",
+            "test_xml/stop_simple.xml",
+        ] {
+            assert!(parse_xml(xml).unwrap().problem().is_none());
+        }
+    }
+
     #[test]
     fn trip_lots() {
         let _ojp = parse_xml("test_xml/trip_lots.xml").unwrap();
     }
 
+    #[cfg(feature = "client")]
     #[tokio::test(flavor = "current_thread")]
     #[test_log::test]
     async fn request_location_information_service_simple() {
@@ -1536,6 +4259,7 @@ mod test {
         let _ojp = OJP::try_from(response.as_str()).unwrap();
     }
 
+    #[cfg(feature = "client")]
     #[tokio::test(flavor = "current_thread")]
     #[test_log::test]
     async fn request_trip_service_simple() {
@@ -0,0 +1,130 @@
+use std::fmt::Display;
+
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::mode::code_enum;
+
+code_enum! {
+    /// Expected occupancy of a vehicle, `OccupancyLevel` in SIRI
+    OccupancyLevel {
+        Empty => "empty",
+        ManySeatsAvailable => "manySeatsAvailable",
+        FewSeatsAvailable => "fewSeatsAvailable",
+        StandingRoomOnly => "standingRoomOnly",
+        CrushedStandingRoomOnly => "crushedStandingRoomOnly",
+        Full => "full",
+        NotAcceptingPassengers => "notAcceptingPassengers",
+        Unknown => "unknown",
+    }
+}
+
+impl OccupancyLevel {
+    /// How crowded the vehicle is, from 0 (empty) to 6 (not accepting passengers). `None` for
+    /// unknown levels.
+    pub fn severity(&self) -> Option<u8> {
+        match self {
+            Self::Empty => Some(0),
+            Self::ManySeatsAvailable => Some(1),
+            Self::FewSeatsAvailable => Some(2),
+            Self::StandingRoomOnly => Some(3),
+            Self::CrushedStandingRoomOnly => Some(4),
+            Self::Full => Some(5),
+            Self::NotAcceptingPassengers => Some(6),
+            Self::Unknown | Self::Other(_) => None,
+        }
+    }
+
+    /// The more crowded of the levels with a known severity
+    pub fn worst<'a>(levels: impl IntoIterator<Item = &'a OccupancyLevel>) -> Option<&'a Self> {
+        levels
+            .into_iter()
+            .filter(|l| l.severity().is_some())
+            .max_by_key(|l| l.severity())
+    }
+}
+
+code_enum! {
+    /// Class of travel, `FareClass` in SIRI
+    FareClass {
+        FirstClass => "firstClass",
+        SecondClass => "secondClass",
+        ThirdClass => "thirdClass",
+        EconomyClass => "economyClass",
+        BusinessClass => "businessClass",
+        PremiumClass => "premiumClass",
+        StandardClass => "standardClass",
+        Unknown => "unknown",
+    }
+}
+
+/// Expected occupancy of a fare class when the vehicle departs from a stop
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[serde(rename_all = "PascalCase")]
+pub struct Occupancy {
+    fare_class: FareClass,
+    occupancy_level: OccupancyLevel,
+}
+
+impl Occupancy {
+    pub fn new(fare_class: FareClass, occupancy_level: OccupancyLevel) -> Self {
+        Occupancy {
+            fare_class,
+            occupancy_level,
+        }
+    }
+
+    pub fn fare_class(&self) -> &FareClass {
+        &self.fare_class
+    }
+
+    pub fn level(&self) -> &OccupancyLevel {
+        &self.occupancy_level
+    }
+}
+
+/// Level of `fare_class` in `occupancies`
+pub(crate) fn level_of<'a>(
+    occupancies: &'a [Occupancy],
+    fare_class: &FareClass,
+) -> Option<&'a OccupancyLevel> {
+    occupancies
+        .iter()
+        .find(|o| &o.fare_class == fare_class)
+        .map(|o| &o.occupancy_level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn worst_level() {
+        let levels = [
+            OccupancyLevel::from_code("fewSeatsAvailable"),
+            OccupancyLevel::Unknown,
+            OccupancyLevel::from_code("standingRoomOnly"),
+            OccupancyLevel::ManySeatsAvailable,
+        ];
+        assert_eq!(
+            OccupancyLevel::worst(&levels),
+            Some(&OccupancyLevel::StandingRoomOnly)
+        );
+        assert_eq!(OccupancyLevel::worst(&[OccupancyLevel::Unknown]), None);
+        assert_eq!(FareClass::from_code("secondClass "), FareClass::SecondClass);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn occupancy_roundtrip() {
+        let occupancy = Occupancy::new(FareClass::SecondClass, OccupancyLevel::StandingRoomOnly);
+        let json = serde_json::to_string(&occupancy).unwrap();
+        assert_eq!(
+            json,
+            r#"{"FareClass":"secondClass","OccupancyLevel":"standingRoomOnly"}"#
+        );
+        assert_eq!(serde_json::from_str::<Occupancy>(&json).unwrap(), occupancy);
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt::Display;
+
+use crate::error::ErrorKind;
+
+/// Problem reported by the OJP service in the `ErrorCondition` of a delivery
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    problem_type: ProblemType,
+    description: Option<String>,
+}
+
+impl Problem {
+    pub fn new(problem_type: ProblemType, description: Option<String>) -> Self {
+        Problem {
+            problem_type,
+            description,
+        }
+    }
+
+    pub fn problem_type(&self) -> &ProblemType {
+        &self.problem_type
+    }
+
+    /// Free text description sent by the service, if any
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.problem_type.code(), self.problem_type)?;
+        if let Some(description) = &self.description {
+            write!(f, ": {description}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Problem codes of the OJP deliveries and the generic SIRI errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProblemType {
+    TripNoTripFound,
+    TripNoRoute,
+    TripOriginUnknown,
+    TripDestinationUnknown,
+    TripViaUnknown,
+    TripNotViaUnknown,
+    TripNoChangeAtUnknown,
+    TripDateTimeError,
+    TripOriginDestinationIdentical,
+    LocationNoResults,
+    LocationUnsupportedType,
+    LocationUnsupportedCombination,
+    StopEventLocationUnserved,
+    StopEventLocationUnknown,
+    StopEventDateOutOfRange,
+    StopEventNoEventFound,
+    /// SIRI `ServiceNotAvailableError`
+    ServiceNotAvailable,
+    /// SIRI `CapabilityNotSupportedError`
+    CapabilityNotSupported,
+    /// SIRI `AccessNotAllowedError`
+    AccessNotAllowed,
+    /// SIRI `NoInfoForTopicError`
+    NoInfoForTopic,
+    /// SIRI `BeyondDataHorizon`
+    BeyondDataHorizon,
+    /// SIRI `UnknownParticipantError`
+    UnknownParticipant,
+    /// SIRI `OtherError` without a known problem code
+    OtherError,
+    /// Any code not known by this crate
+    Other(String),
+}
+
+impl ProblemType {
+    pub fn from_code(code: &str) -> Self {
+        match code.trim() {
+            "TRIP_NOTRIPFOUND" => Self::TripNoTripFound,
+            "TRIP_NOROUTE" => Self::TripNoRoute,
+            "TRIP_ORIGINUNKNOWN" => Self::TripOriginUnknown,
+            "TRIP_DESTINATIONUNKNOWN" => Self::TripDestinationUnknown,
+            "TRIP_VIAUNKNOWN" => Self::TripViaUnknown,
+            "TRIP_NOTVIAUNKNOWN" => Self::TripNotViaUnknown,
+            "TRIP_NOCHANGEATUNKNOWN" => Self::TripNoChangeAtUnknown,
+            "TRIP_DATETIMEERROR" => Self::TripDateTimeError,
+            "TRIP_ORIGINDESTINATIONIDENTICAL" => Self::TripOriginDestinationIdentical,
+            "LOCATION_NORESULTS" => Self::LocationNoResults,
+            "LOCATION_UNSUPPORTEDTYPE" => Self::LocationUnsupportedType,
+            "LOCATION_UNSUPPORTEDCOMBINATION" => Self::LocationUnsupportedCombination,
+            "STOPEVENT_LOCATIONUNSERVED" => Self::StopEventLocationUnserved,
+            "STOPEVENT_LOCATIONUNKNOWN" => Self::StopEventLocationUnknown,
+            "STOPEVENT_DATEOUTOFRANGE" => Self::StopEventDateOutOfRange,
+            "STOPEVENT_NOEVENTFOUND" => Self::StopEventNoEventFound,
+            "ServiceNotAvailableError" => Self::ServiceNotAvailable,
+            "CapabilityNotSupportedError" => Self::CapabilityNotSupported,
+            "AccessNotAllowedError" => Self::AccessNotAllowed,
+            "NoInfoForTopicError" => Self::NoInfoForTopic,
+            "BeyondDataHorizon" => Self::BeyondDataHorizon,
+            "UnknownParticipantError" => Self::UnknownParticipant,
+            "OtherError" => Self::OtherError,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        match self {
+            Self::TripNoTripFound => "TRIP_NOTRIPFOUND",
+            Self::TripNoRoute => "TRIP_NOROUTE",
+            Self::TripOriginUnknown => "TRIP_ORIGINUNKNOWN",
+            Self::TripDestinationUnknown => "TRIP_DESTINATIONUNKNOWN",
+            Self::TripViaUnknown => "TRIP_VIAUNKNOWN",
+            Self::TripNotViaUnknown => "TRIP_NOTVIAUNKNOWN",
+            Self::TripNoChangeAtUnknown => "TRIP_NOCHANGEATUNKNOWN",
+            Self::TripDateTimeError => "TRIP_DATETIMEERROR",
+            Self::TripOriginDestinationIdentical => "TRIP_ORIGINDESTINATIONIDENTICAL",
+            Self::LocationNoResults => "LOCATION_NORESULTS",
+            Self::LocationUnsupportedType => "LOCATION_UNSUPPORTEDTYPE",
+            Self::LocationUnsupportedCombination => "LOCATION_UNSUPPORTEDCOMBINATION",
+            Self::StopEventLocationUnserved => "STOPEVENT_LOCATIONUNSERVED",
+            Self::StopEventLocationUnknown => "STOPEVENT_LOCATIONUNKNOWN",
+            Self::StopEventDateOutOfRange => "STOPEVENT_DATEOUTOFRANGE",
+            Self::StopEventNoEventFound => "STOPEVENT_NOEVENTFOUND",
+            Self::ServiceNotAvailable => "ServiceNotAvailableError",
+            Self::CapabilityNotSupported => "CapabilityNotSupportedError",
+            Self::AccessNotAllowed => "AccessNotAllowedError",
+            Self::NoInfoForTopic => "NoInfoForTopicError",
+            Self::BeyondDataHorizon => "BeyondDataHorizon",
+            Self::UnknownParticipant => "UnknownParticipantError",
+            Self::OtherError => "OtherError",
+            Self::Other(code) => code,
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            _ if self.is_no_result() => ErrorKind::NoData,
+            Self::TripOriginUnknown
+            | Self::TripDestinationUnknown
+            | Self::TripViaUnknown
+            | Self::TripNotViaUnknown
+            | Self::TripNoChangeAtUnknown
+            | Self::TripDateTimeError
+            | Self::TripOriginDestinationIdentical
+            | Self::LocationUnsupportedType
+            | Self::LocationUnsupportedCombination
+            | Self::StopEventLocationUnknown
+            | Self::StopEventDateOutOfRange
+            | Self::CapabilityNotSupported
+            | Self::BeyondDataHorizon => ErrorKind::ClientMisuse,
+            Self::ServiceNotAvailable => ErrorKind::Transient,
+            _ => ErrorKind::ServerRejection,
+        }
+    }
+
+    /// The request was valid, but the service has nothing to return for it
+    pub fn is_no_result(&self) -> bool {
+        matches!(
+            self,
+            Self::TripNoTripFound
+                | Self::TripNoRoute
+                | Self::LocationNoResults
+                | Self::StopEventLocationUnserved
+                | Self::StopEventNoEventFound
+                | Self::NoInfoForTopic
+        )
+    }
+}
+
+impl Display for ProblemType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Self::TripNoTripFound => "no trip found for the request",
+            Self::TripNoRoute => "no route between origin and destination",
+            Self::TripOriginUnknown => "origin is unknown",
+            Self::TripDestinationUnknown => "destination is unknown",
+            Self::TripViaUnknown => "via location is unknown",
+            Self::TripNotViaUnknown => "not-via location is unknown",
+            Self::TripNoChangeAtUnknown => "no-change-at location is unknown",
+            Self::TripDateTimeError => "date and time are invalid or out of range",
+            Self::TripOriginDestinationIdentical => "origin and destination are identical",
+            Self::LocationNoResults => "no location found for the request",
+            Self::LocationUnsupportedType => "location type is not supported",
+            Self::LocationUnsupportedCombination => "combination of restrictions is not supported",
+            Self::StopEventLocationUnserved => "location is not served at the requested time",
+            Self::StopEventLocationUnknown => "location is unknown",
+            Self::StopEventDateOutOfRange => "date is outside of the timetable period",
+            Self::StopEventNoEventFound => "no stop event found for the request",
+            Self::ServiceNotAvailable => "service is not available",
+            Self::CapabilityNotSupported => "capability is not supported",
+            Self::AccessNotAllowed => "access is not allowed",
+            Self::NoInfoForTopic => "no information for the requested topic",
+            Self::BeyondDataHorizon => "request is beyond the data horizon",
+            Self::UnknownParticipant => "participant is unknown",
+            Self::OtherError => "unspecified error",
+            Self::Other(_) => "problem unknown to ojp-rs",
+        };
+        write!(f, "{description}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_roundtrip() {
+        for code in [
+            "TRIP_NOTRIPFOUND",
+            "TRIP_NOROUTE",
+            "TRIP_ORIGINUNKNOWN",
+            "LOCATION_NORESULTS",
+            "STOPEVENT_NOEVENTFOUND",
+            "AccessNotAllowedError",
+            "SOMETHING_NEW",
+        ] {
+            assert_eq!(ProblemType::from_code(code).code(), code);
+        }
+        assert_eq!(
+            ProblemType::from_code("SOMETHING_NEW"),
+            ProblemType::Other("SOMETHING_NEW".to_string())
+        );
+    }
+}
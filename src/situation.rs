@@ -0,0 +1,404 @@
+#![allow(dead_code)]
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::text::{LangText, Text};
+
+/// Items of `iter` in order, without duplicates
+pub(crate) fn dedup<T: PartialEq>(iter: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut items = Vec::new();
+    for item in iter {
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+    items
+}
+
+/// `Situations` of a response context
+#[derive(Deserialize, Debug, Default)]
+pub(crate) struct Situations {
+    #[serde(rename = "PtSituation", default)]
+    pub(crate) pt_situations: Vec<PtSituation>,
+}
+
+/// Reference from a leg, a call or a service to a [`PtSituation`] of the response context
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "PascalCase")]
+pub struct SituationRef {
+    #[serde(default)]
+    participant_ref: String,
+    situation_number: String,
+}
+
+impl SituationRef {
+    pub fn new(participant_ref: &str, situation_number: &str) -> Self {
+        SituationRef {
+            participant_ref: participant_ref.to_string(),
+            situation_number: situation_number.to_string(),
+        }
+    }
+
+    /// Organisation that published the situation, e.g. "ch:1:sboid:100001"
+    pub fn participant_ref(&self) -> &str {
+        &self.participant_ref
+    }
+
+    pub fn situation_number(&self) -> &str {
+        &self.situation_number
+    }
+}
+
+/// `SituationFullRefs` of a service or a call
+#[derive(Deserialize, Debug, Default)]
+pub(crate) struct SituationFullRefs {
+    #[serde(rename = "SituationFullRef", default)]
+    pub(crate) refs: Vec<SituationRef>,
+}
+
+/// Disruption or other information for passengers (SIRI situation exchange)
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct PtSituation {
+    creation_time: DateTime<Utc>,
+    #[serde(default)]
+    participant_ref: String,
+    situation_number: String,
+    version: Option<u32>,
+    source: Option<Source>,
+    #[serde(rename = "ValidityPeriod", default)]
+    validity_periods: Vec<ValidityPeriod>,
+    alert_cause: Option<String>,
+    priority: Option<u32>,
+    scope_type: Option<String>,
+    language: Option<String>,
+    #[serde(default)]
+    publishing_actions: PublishingActions,
+}
+
+impl PtSituation {
+    /// Reference under which legs and services refer to the situation
+    pub fn reference(&self) -> SituationRef {
+        SituationRef::new(&self.participant_ref, &self.situation_number)
+    }
+
+    /// Whether `situation_ref` refers to this situation
+    pub fn is_referenced_by(&self, situation_ref: &SituationRef) -> bool {
+        self.situation_number == situation_ref.situation_number
+            && self.participant_ref == situation_ref.participant_ref
+    }
+
+    pub fn participant_ref(&self) -> &str {
+        &self.participant_ref
+    }
+
+    pub fn situation_number(&self) -> &str {
+        &self.situation_number
+    }
+
+    pub fn creation_time(&self) -> DateTime<Utc> {
+        self.creation_time
+    }
+
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    /// Type of the source of the information, e.g. "directReport"
+    pub fn source_type(&self) -> Option<&str> {
+        Some(self.source.as_ref()?.source_type.as_str())
+    }
+
+    pub fn validity_periods(&self) -> &[ValidityPeriod] {
+        &self.validity_periods
+    }
+
+    /// Whether one of the validity periods contains `time`. A situation without validity period
+    /// is always valid.
+    pub fn is_valid_at(&self, time: DateTime<Utc>) -> bool {
+        self.validity_periods.is_empty() || self.validity_periods.iter().any(|p| p.contains(time))
+    }
+
+    /// Cause of the situation, e.g. "constructionWork"
+    pub fn alert_cause(&self) -> Option<&str> {
+        self.alert_cause.as_deref()
+    }
+
+    /// Priority of the situation, 1 being the highest
+    pub fn priority(&self) -> Option<u32> {
+        self.priority
+    }
+
+    /// What the situation is about, e.g. "line" or "stopPlace"
+    pub fn scope_type(&self) -> Option<&str> {
+        self.scope_type.as_deref()
+    }
+
+    /// Default language of the texts
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Where the texts are meant to be shown, e.g. "general" or "stopPoint", without duplicates
+    pub fn perspectives(&self) -> Vec<&str> {
+        dedup(
+            self.passenger_information_actions()
+                .flat_map(|a| &a.perspectives)
+                .map(String::as_str),
+        )
+    }
+
+    /// Texts of the passenger information actions meant for `perspective`, e.g. "general" or
+    /// "stopPoint". Actions without perspective are meant for all of them.
+    pub fn texts(&self, perspective: &str) -> SituationTexts {
+        let contents: Vec<&TextualContent> = self
+            .passenger_information_actions()
+            .filter(|a| {
+                a.perspectives.is_empty() || a.perspectives.iter().any(|p| p == perspective)
+            })
+            .flat_map(|a| &a.textual_contents)
+            .collect();
+        SituationTexts {
+            summary: single(&contents, |c| Some(&c.summary_content.as_ref()?.texts)),
+            reason: single(&contents, |c| Some(&c.reason_content.as_ref()?.texts)),
+            description: paragraphs(&contents, |c| {
+                c.description_contents.iter().map(|c| &c.texts).collect()
+            }),
+            consequence: paragraphs(&contents, |c| {
+                c.consequence_contents.iter().map(|c| &c.texts).collect()
+            }),
+            recommendation: paragraphs(&contents, |c| {
+                c.recommendation_contents.iter().map(|c| &c.texts).collect()
+            }),
+            duration: single(&contents, |c| Some(&c.duration_content.as_ref()?.texts)),
+        }
+    }
+
+    fn passenger_information_actions(&self) -> impl Iterator<Item = &PassengerInformationAction> {
+        self.publishing_actions
+            .publishing_actions
+            .iter()
+            .flat_map(|p| &p.passenger_information_actions)
+    }
+}
+
+/// Texts of a situation meant for one perspective, see [`PtSituation::texts`]. The service
+/// usually sends one passenger information action per language, they are gathered here.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SituationTexts {
+    summary: Text,
+    reason: Text,
+    description: Vec<Text>,
+    consequence: Vec<Text>,
+    recommendation: Vec<Text>,
+    duration: Text,
+}
+
+impl SituationTexts {
+    /// Short text for banners, e.g. "Tram 11: Unterbruch zwischen Kreuzplatz und Rehalp"
+    pub fn summary(&self) -> &Text {
+        &self.summary
+    }
+
+    pub fn reason(&self) -> &Text {
+        &self.reason
+    }
+
+    /// Paragraphs of the description
+    pub fn description(&self) -> &[Text] {
+        &self.description
+    }
+
+    pub fn consequence(&self) -> &[Text] {
+        &self.consequence
+    }
+
+    pub fn recommendation(&self) -> &[Text] {
+        &self.recommendation
+    }
+
+    pub fn duration(&self) -> &Text {
+        &self.duration
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.summary.is_empty()
+            && self.reason.is_empty()
+            && self.description.is_empty()
+            && self.consequence.is_empty()
+            && self.recommendation.is_empty()
+            && self.duration.is_empty()
+    }
+}
+
+/// Texts of a content that appears at most once per textual content, usually one per language
+fn single<'a>(
+    contents: &[&'a TextualContent],
+    texts: impl Fn(&'a TextualContent) -> Option<&'a Vec<LangText>>,
+) -> Text {
+    Text::from_texts(
+        contents
+            .iter()
+            .filter_map(|c| texts(c))
+            .flatten()
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Texts of a repeated content: the n-th paragraph gathers the n-th content of every textual
+/// content, i.e. its translations
+fn paragraphs<'a>(
+    contents: &[&'a TextualContent],
+    texts: impl Fn(&'a TextualContent) -> Vec<&'a Vec<LangText>>,
+) -> Vec<Text> {
+    let mut paragraphs: Vec<Vec<LangText>> = Vec::new();
+    for content in contents {
+        for (i, texts) in texts(content).into_iter().enumerate() {
+            if paragraphs.len() <= i {
+                paragraphs.push(Vec::new());
+            }
+            paragraphs[i].extend(texts.iter().cloned());
+        }
+    }
+    paragraphs.into_iter().map(Text::from_texts).collect()
+}
+
+/// Period during which a situation applies, open ended if there is no end time
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct ValidityPeriod {
+    start_time: DateTime<Utc>,
+    end_time: Option<DateTime<Utc>>,
+}
+
+impl ValidityPeriod {
+    pub fn start_time(&self) -> DateTime<Utc> {
+        self.start_time
+    }
+
+    pub fn end_time(&self) -> Option<DateTime<Utc>> {
+        self.end_time
+    }
+
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.start_time <= time && self.end_time.is_none_or(|end| time <= end)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct Source {
+    #[serde(default)]
+    source_type: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct PublishingActions {
+    #[serde(rename = "PublishingAction", default)]
+    publishing_actions: Vec<PublishingAction>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PublishingAction {
+    publish_at_scope: Option<PublishAtScope>,
+    #[serde(rename = "PassengerInformationAction", default)]
+    passenger_information_actions: Vec<PassengerInformationAction>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PublishAtScope {
+    scope_type: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct PassengerInformationAction {
+    #[serde(default)]
+    action_ref: String,
+    recorded_at_time: Option<DateTime<Utc>>,
+    #[serde(rename = "Perspective", default)]
+    perspectives: Vec<String>,
+    #[serde(rename = "TextualContent", default)]
+    textual_contents: Vec<TextualContent>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct TextualContent {
+    summary_content: Option<SummaryContent>,
+    reason_content: Option<ReasonContent>,
+    #[serde(rename = "DescriptionContent", default)]
+    description_contents: Vec<DescriptionContent>,
+    #[serde(rename = "ConsequenceContent", default)]
+    consequence_contents: Vec<ConsequenceContent>,
+    #[serde(rename = "RecommendationContent", default)]
+    recommendation_contents: Vec<RecommendationContent>,
+    duration_content: Option<DurationContent>,
+}
+
+#[derive(Deserialize, Debug)]
+struct SummaryContent {
+    #[serde(rename = "SummaryText", default)]
+    texts: Vec<LangText>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ReasonContent {
+    #[serde(rename = "ReasonText", default)]
+    texts: Vec<LangText>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DescriptionContent {
+    #[serde(rename = "DescriptionText", default)]
+    texts: Vec<LangText>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ConsequenceContent {
+    #[serde(rename = "ConsequenceText", default)]
+    texts: Vec<LangText>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RecommendationContent {
+    #[serde(rename = "RecommendationText", default)]
+    texts: Vec<LangText>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DurationContent {
+    #[serde(rename = "DurationText", default)]
+    texts: Vec<LangText>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn situation_without_texts() {
+        let situation: PtSituation = quick_xml::de::from_str(
+            "<PtSituation>
+                <CreationTime>2025-10-17T07:55:00Z</CreationTime>
+                <SituationNumber>42</SituationNumber>
+                <PublishingActions>
+                    <PublishingAction>
+                        <PassengerInformationAction>
+                            <Perspective>general</Perspective>
+                            <Perspective>vehicleJourney</Perspective>
+                        </PassengerInformationAction>
+                    </PublishingAction>
+                </PublishingActions>
+            </PtSituation>",
+        )
+        .unwrap();
+        assert!(situation.texts("general").is_empty());
+        assert_eq!(situation.perspectives(), ["general", "vehicleJourney"]);
+        assert!(situation.is_valid_at(Utc::now()));
+        assert!(situation.is_referenced_by(&SituationRef::new("", "42")));
+    }
+}
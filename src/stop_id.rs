@@ -0,0 +1,324 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::OjpError;
+use crate::country::{self, iso_to_uic, uic_to_iso};
+
+/// Registration authority of the SLOIDs issued for Swiss stops
+const DEFAULT_AGENCY: u32 = 1;
+
+/// Swiss Location ID, e.g. `ch:1:sloid:4414:1:1`: the stop place `ch:1:sloid:4414`, its area
+/// `ch:1:sloid:4414:1` and the quay `1` of that area
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sloid {
+    country: String,
+    agency: u32,
+    number: u32,
+    area: Option<u32>,
+    quay: Option<u32>,
+}
+
+impl Sloid {
+    /// SLOID of a stop place, e.g. `Sloid::new("ch", 1, 3000)` for `ch:1:sloid:3000`
+    pub fn new(country: &str, agency: u32, number: u32) -> Self {
+        Sloid {
+            country: country.to_lowercase(),
+            agency,
+            number,
+            area: None,
+            quay: None,
+        }
+    }
+
+    pub fn with_area(mut self, area: u32) -> Self {
+        self.area = Some(area);
+        self
+    }
+
+    /// Sets the quay, only meaningful together with an area
+    pub fn with_quay(mut self, quay: u32) -> Self {
+        self.quay = Some(quay);
+        self
+    }
+
+    /// ISO 3166 code of the country, in lowercase
+    pub fn country(&self) -> &str {
+        &self.country
+    }
+
+    pub fn agency(&self) -> u32 {
+        self.agency
+    }
+
+    /// Number of the stop place, the last five digits of its DIDOK number
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    pub fn area(&self) -> Option<u32> {
+        self.area
+    }
+
+    pub fn quay(&self) -> Option<u32> {
+        self.quay
+    }
+
+    /// The area of a quay, or the stop place of an area. `None` for a stop place.
+    pub fn parent(&self) -> Option<Sloid> {
+        let mut parent = self.clone();
+        if parent.quay.take().is_none() {
+            parent.area.take()?;
+        }
+        Some(parent)
+    }
+
+    pub fn stop_place(&self) -> Sloid {
+        Sloid::new(&self.country, self.agency, self.number)
+    }
+
+    pub fn is_stop_place(&self) -> bool {
+        self.area.is_none() && self.quay.is_none()
+    }
+
+    /// DIDOK number of the stop place, e.g. 8503000 for `ch:1:sloid:3000:...`
+    pub fn didok(&self) -> Result<i32, OjpError> {
+        let uic = iso_to_uic(&self.country)
+            .ok_or_else(|| OjpError::FailedToConvertIsoCode(self.country.clone()))?;
+        let number = i32::try_from(self.number)
+            .ok()
+            .filter(|n| *n < 100_000)
+            .ok_or_else(|| OjpError::MalformedSloid(self.to_string()))?;
+        Ok(uic * 100_000 + number)
+    }
+}
+
+impl Display for Sloid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:sloid:{}", self.country, self.agency, self.number)?;
+        if let Some(area) = self.area {
+            write!(f, ":{area}")?;
+        }
+        if let Some(quay) = self.quay {
+            write!(f, ":{quay}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Sloid {
+    type Err = OjpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || OjpError::MalformedSloid(s.to_string());
+        let parts: Vec<&str> = s.trim().split(':').collect();
+        if !(4..=6).contains(&parts.len()) || parts[2] != "sloid" {
+            return Err(malformed());
+        }
+        if !country::is_known(parts[0]) {
+            return Err(OjpError::FailedToConvertIsoCode(parts[0].to_string()));
+        }
+        let number = |part: &str| part.parse::<u32>().map_err(|_| malformed());
+        let mut sloid = Sloid::new(parts[0], number(parts[1])?, number(parts[3])?);
+        sloid.area = parts.get(4).map(|p| number(p)).transpose()?;
+        sloid.quay = parts.get(5).map(|p| number(p)).transpose()?;
+        Ok(sloid)
+    }
+}
+
+/// Converts a DIDOK number to the SLOID of its stop place, e.g. 8503000 to `ch:1:sloid:3000`
+pub fn didok_to_sloid(didok: i32) -> Result<Sloid, OjpError> {
+    let uic = didok / 100_000;
+    let country = uic_to_iso(uic).ok_or_else(|| OjpError::MalformedStopId(didok.to_string()))?;
+    Ok(Sloid::new(
+        country,
+        DEFAULT_AGENCY,
+        (didok % 100_000) as u32,
+    ))
+}
+
+/// Identifier of a stop as sent or accepted by the OJP service
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StopId {
+    /// DIDOK (UIC) number of a stop place, e.g. 8503000
+    Didok(i32),
+    Sloid(Sloid),
+}
+
+impl StopId {
+    /// DIDOK number of the stop place
+    pub fn didok(&self) -> Result<i32, OjpError> {
+        match self {
+            Self::Didok(didok) => Ok(*didok),
+            Self::Sloid(sloid) => sloid.didok(),
+        }
+    }
+
+    /// UIC country code, the first two digits of the DIDOK number
+    pub fn uic_country_code(&self) -> Result<i32, OjpError> {
+        Ok(self.didok()? / 100_000)
+    }
+
+    /// Lowercase ISO 3166 alpha-2 code of the country of the stop, e.g. "de" for 8000105
+    pub fn country(&self) -> Option<&'static str> {
+        match self {
+            Self::Didok(didok) => country::country_of_didok(*didok),
+            Self::Sloid(sloid) => country::iso_code(&sloid.country),
+        }
+    }
+
+    pub fn sloid(&self) -> Result<Sloid, OjpError> {
+        match self {
+            Self::Didok(didok) => didok_to_sloid(*didok),
+            Self::Sloid(sloid) => Ok(sloid.clone()),
+        }
+    }
+
+    /// See [`Sloid::parent`]. A DIDOK number always denotes a stop place and has no parent.
+    pub fn parent(&self) -> Option<StopId> {
+        match self {
+            Self::Didok(_) => None,
+            Self::Sloid(sloid) => sloid.parent().map(StopId::Sloid),
+        }
+    }
+
+    pub fn stop_place(&self) -> StopId {
+        match self {
+            Self::Didok(didok) => Self::Didok(*didok),
+            Self::Sloid(sloid) => Self::Sloid(sloid.stop_place()),
+        }
+    }
+
+    /// Whether both ids belong to the same stop place, e.g. two quays of a station
+    pub fn same_stop_place(&self, other: &StopId) -> bool {
+        match (self.didok(), other.didok()) {
+            (Ok(lhs), Ok(rhs)) => lhs == rhs,
+            _ => self.stop_place() == other.stop_place(),
+        }
+    }
+}
+
+impl Display for StopId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Didok(didok) => write!(f, "{didok}"),
+            Self::Sloid(sloid) => write!(f, "{sloid}"),
+        }
+    }
+}
+
+impl FromStr for StopId {
+    type Err = OjpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.contains(":sloid:") {
+            return Ok(Self::Sloid(s.parse()?));
+        }
+        s.parse::<i32>()
+            .map(Self::Didok)
+            .map_err(|_| OjpError::MalformedStopId(s.to_string()))
+    }
+}
+
+/// Serialized as written by the service, e.g. "8503000" or "ch:1:sloid:3000:4:6"
+#[cfg(feature = "serde")]
+impl Serialize for StopId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for StopId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl From<i32> for StopId {
+    fn from(didok: i32) -> Self {
+        StopId::Didok(didok)
+    }
+}
+
+impl From<Sloid> for StopId {
+    fn from(sloid: Sloid) -> Self {
+        StopId::Sloid(sloid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sloid_roundtrip() {
+        for s in [
+            "ch:1:sloid:4414",
+            "ch:1:sloid:4414:1",
+            "ch:1:sloid:4414:1:1",
+        ] {
+            assert_eq!(s.parse::<StopId>().unwrap().to_string(), s);
+        }
+        assert_eq!("8503000".parse::<StopId>().unwrap(), StopId::Didok(8503000));
+        assert!("ch:1:sloid".parse::<StopId>().is_err());
+        assert!("ch:1:sloid:12:a".parse::<StopId>().is_err());
+        assert!("Zürich HB".parse::<StopId>().is_err());
+    }
+
+    #[test]
+    fn quay_keeps_parents() {
+        let quay: StopId = "ch:1:sloid:3088:0:99992".parse().unwrap();
+        let StopId::Sloid(sloid) = &quay else {
+            panic!("expected a sloid");
+        };
+        assert_eq!(sloid.area(), Some(0));
+        assert_eq!(sloid.quay(), Some(99992));
+        let area = quay.parent().unwrap();
+        assert_eq!(area.to_string(), "ch:1:sloid:3088:0");
+        let stop_place = area.parent().unwrap();
+        assert_eq!(stop_place, quay.stop_place());
+        assert!(stop_place.parent().is_none());
+        assert!(quay.same_stop_place(&StopId::Didok(8503088)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_as_string() {
+        let quay: StopId = "ch:1:sloid:3006:4:6".parse().unwrap();
+        let json = serde_json::to_string(&quay).unwrap();
+        assert_eq!(json, r#""ch:1:sloid:3006:4:6""#);
+        assert_eq!(serde_json::from_str::<StopId>(&json).unwrap(), quay);
+        assert_eq!(
+            serde_json::from_str::<StopId>(r#""8503000""#).unwrap(),
+            StopId::Didok(8503000)
+        );
+        assert!(serde_json::from_str::<StopId>(r#""Zürich HB""#).is_err());
+    }
+
+    #[test]
+    fn didok_conversions() {
+        let quay: StopId = "ch:1:sloid:3006:4:6".parse().unwrap();
+        assert_eq!(quay.didok().unwrap(), 8503006);
+        assert_eq!(quay.uic_country_code().unwrap(), 85);
+        let sloid = didok_to_sloid(8503006).unwrap();
+        assert_eq!(sloid.to_string(), "ch:1:sloid:3006");
+        assert_eq!(StopId::Didok(8503006).sloid().unwrap(), sloid);
+        assert_eq!(didok_to_sloid(8000105).unwrap().country(), "de");
+        assert_eq!(StopId::Didok(8000105).country(), Some("de"));
+        assert_eq!(
+            "li:1:sloid:12".parse::<StopId>().unwrap().country(),
+            Some("li")
+        );
+        assert!(matches!(
+            "xx:1:sloid:12".parse::<StopId>(),
+            Err(OjpError::FailedToConvertIsoCode(_))
+        ));
+        assert!(didok_to_sloid(1234).is_err());
+    }
+}
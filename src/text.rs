@@ -0,0 +1,107 @@
+use std::fmt::Display;
+
+use serde::Deserialize;
+
+/// Text in one or several languages, e.g.
+/// `<Name><Text xml:lang="de">Zug</Text><Text xml:lang="fr">Train</Text></Name>`.
+/// The first text is the one in the default language of the response.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Text {
+    #[serde(rename = "Text", default)]
+    texts: Vec<LangText>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub(crate) struct LangText {
+    #[serde(rename = "@xml:lang")]
+    lang: Option<String>,
+    #[serde(rename = "$text", default)]
+    text: String,
+}
+
+impl Text {
+    pub(crate) fn from_texts(texts: Vec<LangText>) -> Self {
+        Text { texts }
+    }
+
+    /// Text in the default language of the response, empty if there is none
+    pub fn text(&self) -> &str {
+        self.texts.first().map_or("", |t| t.text.as_str())
+    }
+
+    /// Language tag of [`Text::text`]
+    pub fn lang(&self) -> Option<&str> {
+        self.texts.first()?.lang.as_deref()
+    }
+
+    /// Text in `lang`. Tags match case-insensitively and by primary language, i.e. "de" matches
+    /// "de-CH" and the other way round.
+    pub fn get(&self, lang: &str) -> Option<&str> {
+        self.texts
+            .iter()
+            .find(|t| t.lang.as_deref().is_some_and(|l| same_language(l, lang)))
+            .map(|t| t.text.as_str())
+    }
+
+    /// Text in the first language of `languages` that is available, otherwise in the default
+    /// language
+    pub fn best(&self, languages: &[&str]) -> &str {
+        languages
+            .iter()
+            .find_map(|lang| self.get(lang))
+            .unwrap_or_else(|| self.text())
+    }
+
+    /// Language tags of the available texts
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.texts.iter().filter_map(|t| t.lang.as_deref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text().is_empty()
+    }
+}
+
+impl Display for Text {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text())
+    }
+}
+
+fn same_language(lhs: &str, rhs: &str) -> bool {
+    let primary = |tag: &str| {
+        tag.split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase()
+    };
+    lhs.eq_ignore_ascii_case(rhs) || primary(lhs) == primary(rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_language() {
+        let text: Text = quick_xml::de::from_str(
+            r#"<Name><Text xml:lang="de">Zug</Text><Text xml:lang="fr-CH">Train</Text></Name>"#,
+        )
+        .unwrap();
+        assert_eq!(text.text(), "Zug");
+        assert_eq!(text.lang(), Some("de"));
+        assert_eq!(text.get("fr"), Some("Train"));
+        assert_eq!(text.best(&["it", "fr", "de"]), "Train");
+        assert_eq!(text.best(&["it"]), "Zug");
+        assert_eq!(text.languages().collect::<Vec<_>>(), ["de", "fr-CH"]);
+    }
+
+    #[test]
+    fn text_without_language() {
+        let text: Text = quick_xml::de::from_str("<Name><Text>Bern</Text></Name>").unwrap();
+        assert_eq!(text.best(&["de"]), "Bern");
+        assert!(text.lang().is_none());
+        let empty: Text = quick_xml::de::from_str("<Name/>").unwrap();
+        assert!(empty.is_empty());
+    }
+}
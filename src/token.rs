@@ -0,0 +1,243 @@
+use std::env::VarError;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use secrecy::SecretString;
+use thiserror::Error;
+
+/// Default directory where Docker and Kubernetes mount secrets
+const SECRETS_DIR: &str = "/run/secrets";
+
+#[derive(Debug, Error)]
+pub enum TokenError {
+    #[error("Failed to read environment variable {name}: {source}")]
+    EnvVar { name: String, source: VarError },
+    #[error("Failed to read token file {path}: {source}")]
+    File {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Token file {0} is empty")]
+    EmptyFile(PathBuf),
+    #[error("Token callback failed: {0}")]
+    Callback(String),
+}
+
+/// Source of the API token used to authenticate against the OJP service.
+///
+/// `token` is called before every request, `refresh` once after the server answered
+/// with `401 Unauthorized`. Providers that cache their token should bypass the cache in
+/// `refresh`.
+///
+/// The network entry points (`OJP::find_*`, `RequestBuilder::send_request_with` and their
+/// `blocking` versions) take one as `token_provider`. A plain `&str` is the name of the
+/// environment variable holding the token.
+pub trait TokenProvider: Send + Sync {
+    fn token(&self) -> Result<SecretString, TokenError>;
+
+    fn refresh(&self) -> Result<SecretString, TokenError> {
+        self.token()
+    }
+}
+
+/// A plain string is interpreted as the name of the environment variable holding the token
+impl TokenProvider for str {
+    fn token(&self) -> Result<SecretString, TokenError> {
+        EnvToken::new(self).token()
+    }
+}
+
+impl TokenProvider for String {
+    fn token(&self) -> Result<SecretString, TokenError> {
+        self.as_str().token()
+    }
+}
+
+impl<T: TokenProvider + ?Sized> TokenProvider for &T {
+    fn token(&self) -> Result<SecretString, TokenError> {
+        (**self).token()
+    }
+
+    fn refresh(&self) -> Result<SecretString, TokenError> {
+        (**self).refresh()
+    }
+}
+
+impl<T: TokenProvider + ?Sized> TokenProvider for std::sync::Arc<T> {
+    fn token(&self) -> Result<SecretString, TokenError> {
+        (**self).token()
+    }
+
+    fn refresh(&self) -> Result<SecretString, TokenError> {
+        (**self).refresh()
+    }
+}
+
+/// Reads the token from an environment variable on every call
+#[derive(Debug, Clone)]
+pub struct EnvToken {
+    name: String,
+}
+
+impl EnvToken {
+    pub fn new(name: &str) -> Self {
+        EnvToken {
+            name: name.to_string(),
+        }
+    }
+}
+
+impl TokenProvider for EnvToken {
+    fn token(&self) -> Result<SecretString, TokenError> {
+        let t = std::env::var(&self.name).map_err(|source| TokenError::EnvVar {
+            name: self.name.clone(),
+            source,
+        })?;
+        Ok(SecretString::new(t.into()))
+    }
+}
+
+/// Reads the token from a file on every call, so that rotated secrets are picked up.
+/// Leading and trailing whitespace (e.g. the final newline) is ignored.
+#[derive(Debug, Clone)]
+pub struct FileToken {
+    path: PathBuf,
+}
+
+impl FileToken {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        FileToken {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Token mounted as a Docker or Kubernetes secret, i.e. `/run/secrets/<name>`
+    pub fn secret(name: &str) -> Self {
+        Self::new(Path::new(SECRETS_DIR).join(name))
+    }
+}
+
+impl TokenProvider for FileToken {
+    fn token(&self) -> Result<SecretString, TokenError> {
+        let content = std::fs::read_to_string(&self.path).map_err(|source| TokenError::File {
+            path: self.path.clone(),
+            source,
+        })?;
+        let content = content.trim();
+        if content.is_empty() {
+            return Err(TokenError::EmptyFile(self.path.clone()));
+        }
+        Ok(SecretString::new(content.into()))
+    }
+}
+
+/// A fixed token, e.g. obtained from a vault at startup
+#[derive(Debug, Clone)]
+pub struct StaticToken {
+    token: SecretString,
+}
+
+impl StaticToken {
+    pub fn new(token: SecretString) -> Self {
+        StaticToken { token }
+    }
+}
+
+impl TokenProvider for StaticToken {
+    fn token(&self) -> Result<SecretString, TokenError> {
+        Ok(self.token.clone())
+    }
+}
+
+type TokenCallback = dyn Fn() -> Result<SecretString, TokenError> + Send + Sync;
+
+/// Obtains the token from a callback and caches it until the server rejects it
+pub struct RefreshingToken {
+    callback: Box<TokenCallback>,
+    cached: Mutex<Option<SecretString>>,
+}
+
+impl RefreshingToken {
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn() -> Result<SecretString, TokenError> + Send + Sync + 'static,
+    {
+        RefreshingToken {
+            callback: Box::new(callback),
+            cached: Mutex::new(None),
+        }
+    }
+}
+
+impl Debug for RefreshingToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RefreshingToken")
+            .field("cached", &self.cached)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TokenProvider for RefreshingToken {
+    fn token(&self) -> Result<SecretString, TokenError> {
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(token) = cached.as_ref() {
+            return Ok(token.clone());
+        }
+        let token = (self.callback)()?;
+        *cached = Some(token.clone());
+        Ok(token)
+    }
+
+    fn refresh(&self) -> Result<SecretString, TokenError> {
+        let token = (self.callback)()?;
+        *self.cached.lock().unwrap_or_else(|e| e.into_inner()) = Some(token.clone());
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secrecy::ExposeSecret;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn file_token_is_trimmed() {
+        let path = std::env::temp_dir().join("ojp_rs_file_token_is_trimmed");
+        std::fs::write(&path, "  secret-token\n").unwrap();
+        let token = FileToken::new(&path).token().unwrap();
+        assert_eq!(token.expose_secret(), "secret-token");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn empty_file_token_is_rejected() {
+        let path = std::env::temp_dir().join("ojp_rs_empty_file_token_is_rejected");
+        std::fs::write(&path, "\n").unwrap();
+        let res = FileToken::new(&path).token();
+        assert!(matches!(res, Err(TokenError::EmptyFile(_))));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn missing_env_token() {
+        let res = "OJP_RS_THIS_VARIABLE_DOES_NOT_EXIST".token();
+        assert!(matches!(res, Err(TokenError::EnvVar { .. })));
+    }
+
+    #[test]
+    fn refreshing_token_is_cached_until_refresh() {
+        let calls = std::sync::Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let provider = RefreshingToken::new(move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            Ok(SecretString::new(format!("token-{n}").into()))
+        });
+        assert_eq!(provider.token().unwrap().expose_secret(), "token-0");
+        assert_eq!(provider.token().unwrap().expose_secret(), "token-0");
+        assert_eq!(provider.refresh().unwrap().expose_secret(), "token-1");
+        assert_eq!(provider.token().unwrap().expose_secret(), "token-1");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}